@@ -1,5 +1,6 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use launchpad_types::events::TokenMetadataUpdated;
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
@@ -78,6 +79,18 @@ impl Database {
         Ok(token)
     }
 
+    pub async fn get_token_by_symbol(&self, symbol: &str) -> Result<Option<Token>> {
+        let token = sqlx::query_as!(
+            Token,
+            "SELECT * FROM tokens WHERE symbol = $1",
+            symbol
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(token)
+    }
+
     pub async fn update_token_state(
         &self,
         token_id: Uuid,
@@ -107,6 +120,31 @@ impl Database {
         Ok(())
     }
 
+    pub async fn update_token_metadata(
+        &self,
+        token_id: Uuid,
+        metadata: &TokenMetadataUpdated<String, Vec<String>, String>,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE tokens 
+            SET image_url = $2, description = $3, website = $4, socials = $5,
+                metadata_hash = $6, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            token_id,
+            metadata.image_uri,
+            metadata.description,
+            Some(metadata.website.as_str()).filter(|website| !website.is_empty()),
+            &metadata.socials,
+            metadata.content_hash,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     // Purchase operations
     pub async fn create_purchase(&self, purchase: &Purchase) -> Result<()> {
         sqlx::query!(
@@ -392,6 +430,8 @@ impl Database {
                 symbol: row.symbol,
                 image_url: row.image_url,
                 description: row.description,
+                website: row.website,
+                socials: row.socials.unwrap_or_default(),
                 current_price: row.current_price.to_string(),
                 market_cap: row.market_cap.to_string(),
                 volume_24h: row.volume_24h.to_string(),
@@ -409,7 +449,7 @@ impl Database {
                 issuer_address: row.issuer_address,
                 creator_address: row.creator_address,
                 bonding_curve: BondingCurveInfo {
-                    curve_type: row.curve_type,
+                    curve_type: parse_curve_type(&row.curve_type)?,
                     base_price: row.base_price.to_string(),
                     price_multiplier: row.price_multiplier.to_string(),
                 },
@@ -1,8 +1,13 @@
 use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
+use launchpad_types::{
+    events::{TokenCreated, TokenLaunched, TokenMetadataUpdated, TokenPurchased},
+    BondingCurve, CurveParameters, CurveType,
+};
 use reqwest::Client;
 use serde_json::Value;
 use std::time::Duration;
+use stellar_xdr::curr::{Limits, ReadXdr, ScVal};
 use tokio::time::sleep;
 use tracing::{info, error, warn, debug};
 use uuid::Uuid;
@@ -211,6 +216,10 @@ impl HorizonIndexer {
                     operation, parameters, timestamp
                 )?)))
             }
+            "set_token_metadata" => {
+                Ok(self.parse_token_metadata_event(operation, parameters, timestamp)?
+                    .map(ContractEvent::TokenMetadata))
+            }
             _ => Ok(None),
         }
     }
@@ -230,13 +239,22 @@ impl HorizonIndexer {
                 .unwrap_or("")
                 .to_string(),
             token_address: "".to_string(), // Would be parsed from event data
-            creator: operation["source_account"]
-                .as_str()
-                .unwrap_or("")
-                .to_string(),
-            name: "".to_string(), // Would be parsed from event data
-            symbol: "".to_string(), // Would be parsed from event data
-            total_supply: 0, // Would be parsed from event data
+            created: TokenCreated {
+                token_id: "".to_string(), // Would be parsed from event data
+                creator: operation["source_account"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string(),
+                name: "".to_string(), // Would be parsed from event data
+                symbol: "".to_string(), // Would be parsed from event data
+                total_supply: 0, // Would be parsed from event data
+                quote_asset: "".to_string(), // Would be parsed from event data
+                curve_params: CurveParameters { // Would be parsed from event data
+                    curve_type: CurveType::Linear,
+                    base_price: 0,
+                    price_multiplier: 0,
+                },
+            },
             timestamp,
         })
     }
@@ -253,13 +271,15 @@ impl HorizonIndexer {
                 .unwrap_or("")
                 .to_string(),
             token_address: "".to_string(),
-            buyer: operation["source_account"]
-                .as_str()
-                .unwrap_or("")
-                .to_string(),
-            xlm_amount: 0,
-            tokens_received: 0,
-            new_price: 0,
+            purchase: TokenPurchased {
+                token_id: "".to_string(),
+                buyer: operation["source_account"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string(),
+                quote_spent: 0,
+                tokens_received: 0,
+            },
             timestamp,
         })
     }
@@ -276,13 +296,48 @@ impl HorizonIndexer {
                 .unwrap_or("")
                 .to_string(),
             token_address: "".to_string(),
-            final_price: 0,
-            xlm_raised: 0,
-            tokens_sold: 0,
+            launch: TokenLaunched {
+                token_id: "".to_string(),
+                final_price: 0,
+                quote_raised: 0,
+                tokens_sold: 0,
+            },
             timestamp,
         })
     }
 
+    /// Decode the `token_id` and `metadata` arguments of a `set_token_metadata` call
+    ///
+    /// Returns `None` when they are not the expected XDR values, so a call that
+    /// cannot be read never overwrites stored metadata.
+    fn parse_token_metadata_event(
+        &self,
+        operation: &Value,
+        parameters: &[Value],
+        timestamp: DateTime<Utc>,
+    ) -> Result<Option<TokenMetadataEvent>> {
+        let transaction_hash = operation["transaction_hash"]
+            .as_str()
+            .unwrap_or("")
+            .to_string();
+
+        // Call arguments follow the contract id and function name
+        let metadata = match parameters {
+            [.., token_id, metadata] => decode_token_metadata(token_id, metadata),
+            _ => None,
+        };
+        let Some(metadata) = metadata else {
+            warn!("Skipping undecodable set_token_metadata call in {}", transaction_hash);
+            return Ok(None);
+        };
+
+        Ok(Some(TokenMetadataEvent {
+            transaction_hash,
+            metadata,
+            timestamp,
+        }))
+    }
+
     async fn handle_contract_event(&self, event: ContractEvent, tx_hash: &str) -> Result<()> {
         match event {
             ContractEvent::TokenCreated(event) => {
@@ -294,6 +349,9 @@ impl HorizonIndexer {
             ContractEvent::TokenLaunch(event) => {
                 self.handle_token_launch_event(event).await?;
             }
+            ContractEvent::TokenMetadata(event) => {
+                self.handle_token_metadata_event(event).await?;
+            }
         }
 
         info!("Processed contract event in transaction: {}", tx_hash);
@@ -301,29 +359,39 @@ impl HorizonIndexer {
     }
 
     async fn handle_token_created_event(&self, event: TokenCreatedEvent) -> Result<()> {
+        let created = event.created;
+        let curve = &created.curve_params;
+
+        // Price the token exactly as the contract does
+        let current_price = BondingCurve::get_current_price(curve, 0, created.total_supply)
+            .map_err(|e| anyhow!("Invalid curve for {}: {:?}", created.symbol, e))?;
+
         // Create token record in database
         let token = Token {
             id: Uuid::new_v4(),
-            asset_code: event.symbol.clone(),
+            asset_code: created.symbol.clone(),
             issuer_address: event.token_address.clone(),
             distribution_address: "".to_string(), // Would be extracted from event
             contract_address: self.contract_address.clone(),
-            creator_address: event.creator,
-            name: event.name,
-            symbol: event.symbol,
+            creator_address: created.creator,
+            name: created.name,
+            symbol: created.symbol,
             image_url: None,
             description: None,
-            total_supply: event.total_supply,
+            website: None,
+            socials: None,
+            metadata_hash: None,
+            total_supply: to_db_amount(created.total_supply)?,
             tokens_sold: 0,
             xlm_raised: 0,
-            current_price: 0, // Would be calculated from curve params
+            current_price: to_db_amount(current_price)?,
             launch_threshold_xlm: 0, // Would be extracted from event
             launch_threshold_percent: 0, // Would be extracted from event
             is_launched: false,
             launched_at: None,
-            curve_type: "linear".to_string(), // Would be extracted from event
-            base_price: 0, // Would be extracted from event
-            price_multiplier: 0, // Would be extracted from event
+            curve_type: curve.curve_type.to_string(),
+            base_price: to_db_amount(curve.base_price)?,
+            price_multiplier: to_db_amount(curve.price_multiplier)?,
             created_at: event.timestamp,
             updated_at: event.timestamp,
         };
@@ -341,15 +409,18 @@ impl HorizonIndexer {
             .await?
             .ok_or_else(|| anyhow!("Token not found: {}", event.token_address))?;
 
+        let xlm_amount = to_db_amount(event.purchase.quote_spent)?;
+        let tokens_received = to_db_amount(event.purchase.tokens_received)?;
+
         // Create purchase record
         let purchase = Purchase {
             id: Uuid::new_v4(),
             token_id: token.id,
-            buyer_address: event.buyer.clone(),
-            xlm_amount: event.xlm_amount,
-            tokens_received: event.tokens_received,
-            price_per_token: if event.tokens_received > 0 {
-                event.xlm_amount / event.tokens_received
+            buyer_address: event.purchase.buyer.clone(),
+            xlm_amount,
+            tokens_received,
+            price_per_token: if tokens_received > 0 {
+                xlm_amount / tokens_received
             } else {
                 0
             },
@@ -363,22 +434,28 @@ impl HorizonIndexer {
         self.database
             .upsert_holder(
                 token.id,
-                &event.buyer,
-                event.tokens_received,
-                event.tokens_received,
+                &event.purchase.buyer,
+                tokens_received,
+                tokens_received,
             )
             .await?;
 
-        // Update token state
-        let new_tokens_sold = token.tokens_sold + event.tokens_received;
-        let new_xlm_raised = token.xlm_raised + event.xlm_amount;
+        // Update token state, pricing the curve exactly as the contract does
+        let new_tokens_sold = token.tokens_sold + tokens_received;
+        let new_xlm_raised = token.xlm_raised + xlm_amount;
+        let new_price = BondingCurve::get_current_price(
+            &token.curve_params()?,
+            new_tokens_sold as i128,
+            token.total_supply as i128,
+        )
+        .map_err(|e| anyhow!("Invalid curve for {}: {:?}", token.symbol, e))?;
 
         self.database
             .update_token_state(
                 token.id,
                 new_tokens_sold,
                 new_xlm_raised,
-                event.new_price,
+                to_db_amount(new_price)?,
                 false, // Launch status would be determined from event
                 None,
             )
@@ -387,7 +464,7 @@ impl HorizonIndexer {
         // Update metrics
         self.database.update_token_metrics(token.id).await?;
 
-        info!("Processed purchase: {} tokens for {} XLM", event.tokens_received, event.xlm_amount);
+        info!("Processed purchase: {} tokens for {} XLM", tokens_received, xlm_amount);
 
         Ok(())
     }
@@ -403,9 +480,9 @@ impl HorizonIndexer {
         self.database
             .update_token_state(
                 token.id,
-                event.tokens_sold,
-                event.xlm_raised,
-                event.final_price,
+                to_db_amount(event.launch.tokens_sold)?,
+                to_db_amount(event.launch.quote_raised)?,
+                to_db_amount(event.launch.final_price)?,
                 true,
                 Some(event.timestamp),
             )
@@ -414,7 +491,21 @@ impl HorizonIndexer {
         // Update metrics
         self.database.update_token_metrics(token.id).await?;
 
-        info!("Token launched: {} at final price {}", token.symbol, event.final_price);
+        info!("Token launched: {} at final price {}", token.symbol, event.launch.final_price);
+
+        Ok(())
+    }
+
+    async fn handle_token_metadata_event(&self, event: TokenMetadataEvent) -> Result<()> {
+        // The call only carries the token id, which is the token's symbol
+        let token = self.database
+            .get_token_by_symbol(&event.metadata.token_id)
+            .await?
+            .ok_or_else(|| anyhow!("Token not found: {}", event.metadata.token_id))?;
+
+        self.database.update_token_metadata(token.id, &event.metadata).await?;
+
+        info!("Updated metadata for token: {}", token.symbol);
 
         Ok(())
     }
@@ -431,4 +522,52 @@ enum ContractEvent {
     TokenCreated(TokenCreatedEvent),
     TokenPurchase(TokenPurchaseEvent),
     TokenLaunch(TokenLaunchEvent),
-}
\ No newline at end of file
+    TokenMetadata(TokenMetadataEvent),
+}
+
+/// Decode a base64 XDR `ScVal` call argument as reported by Horizon
+fn decode_sc_val(parameter: &Value) -> Option<ScVal> {
+    ScVal::from_xdr_base64(parameter["value"].as_str()?, Limits::none()).ok()
+}
+
+fn decode_sc_string(value: &ScVal) -> Option<String> {
+    match value {
+        ScVal::String(string) => string.0.to_utf8_string().ok(),
+        _ => None,
+    }
+}
+
+/// Decode a `TokenMetadata` struct argument, stored as a symbol-keyed map
+fn decode_token_metadata(
+    token_id: &Value,
+    metadata: &Value,
+) -> Option<TokenMetadataUpdated<String, Vec<String>, String>> {
+    let token_id = decode_sc_string(&decode_sc_val(token_id)?)?;
+    let ScVal::Map(Some(fields)) = decode_sc_val(metadata)? else {
+        return None;
+    };
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|entry| matches!(&entry.key, ScVal::Symbol(key) if key.0.as_slice() == name.as_bytes()))
+            .map(|entry| &entry.val)
+    };
+
+    let socials = match field("socials")? {
+        ScVal::Vec(Some(links)) => links.iter().map(decode_sc_string).collect::<Option<Vec<_>>>()?,
+        _ => return None,
+    };
+    let content_hash = match field("content_hash")? {
+        ScVal::Bytes(hash) => hash.iter().map(|byte| format!("{:02x}", byte)).collect(),
+        _ => return None,
+    };
+
+    Some(TokenMetadataUpdated {
+        token_id,
+        image_uri: decode_sc_string(field("image_uri")?)?,
+        description: decode_sc_string(field("description")?)?,
+        website: decode_sc_string(field("website")?)?,
+        socials,
+        content_hash,
+    })
+}
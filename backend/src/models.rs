@@ -1,4 +1,9 @@
+use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
+use launchpad_types::{
+    events::{TokenCreated, TokenLaunched, TokenMetadataUpdated, TokenPurchased},
+    CurveParameters, CurveType,
+};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
@@ -17,6 +22,9 @@ pub struct Token {
     pub symbol: String,
     pub image_url: Option<String>,
     pub description: Option<String>,
+    pub website: Option<String>,
+    pub socials: Option<Vec<String>>,
+    pub metadata_hash: Option<String>,
     
     // Supply and economics
     pub total_supply: i64,
@@ -40,6 +48,17 @@ pub struct Token {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Token {
+    /// The token's bonding curve, as configured on the contract
+    pub fn curve_params(&self) -> Result<CurveParameters> {
+        Ok(CurveParameters {
+            curve_type: parse_curve_type(&self.curve_type)?,
+            base_price: self.base_price as i128,
+            price_multiplier: self.price_multiplier as i128,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Purchase {
     pub id: Uuid,
@@ -99,6 +118,8 @@ pub struct TokenDetails {
     pub symbol: String,
     pub image_url: Option<String>,
     pub description: Option<String>,
+    pub website: Option<String>,
+    pub socials: Vec<String>,
     pub current_price: String,
     pub market_cap: String,
     pub volume_24h: String,
@@ -122,7 +143,7 @@ pub struct TokenDetails {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BondingCurveInfo {
-    pub curve_type: String,
+    pub curve_type: CurveType,
     pub base_price: String,
     pub price_multiplier: String,
 }
@@ -139,10 +160,7 @@ pub struct PriceHistory {
 pub struct TokenCreatedEvent {
     pub transaction_hash: String,
     pub token_address: String,
-    pub creator: String,
-    pub name: String,
-    pub symbol: String,
-    pub total_supply: i64,
+    pub created: TokenCreated<String, String>,
     pub timestamp: DateTime<Utc>,
 }
 
@@ -150,10 +168,7 @@ pub struct TokenCreatedEvent {
 pub struct TokenPurchaseEvent {
     pub transaction_hash: String,
     pub token_address: String,
-    pub buyer: String,
-    pub xlm_amount: i64,
-    pub tokens_received: i64,
-    pub new_price: i64,
+    pub purchase: TokenPurchased<String, String>,
     pub timestamp: DateTime<Utc>,
 }
 
@@ -161,8 +176,23 @@ pub struct TokenPurchaseEvent {
 pub struct TokenLaunchEvent {
     pub transaction_hash: String,
     pub token_address: String,
-    pub final_price: i64,
-    pub xlm_raised: i64,
-    pub tokens_sold: i64,
+    pub launch: TokenLaunched<String>,
     pub timestamp: DateTime<Utc>,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenMetadataEvent {
+    pub transaction_hash: String,
+    pub metadata: TokenMetadataUpdated<String, Vec<String>, String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Parse a curve type stored in the database
+pub fn parse_curve_type(name: &str) -> Result<CurveType> {
+    name.parse().map_err(|_| anyhow!("Unknown curve type: {}", name))
+}
+
+/// Convert a contract amount to the database's BIGINT columns
+pub fn to_db_amount(amount: i128) -> Result<i64> {
+    i64::try_from(amount).map_err(|_| anyhow!("Amount out of range: {}", amount))
+}
@@ -0,0 +1,411 @@
+use soroban_sdk::{contract, contractclient, contractimpl, Address, BytesN, Env, String, Vec};
+use crate::{
+    errors::ContractError,
+    storage,
+    types::{CurveSource, Governance, LaunchInstance, LaunchOptions, TradeLimits},
+};
+
+const MAX_PAGE_SIZE: u32 = 100; // Instances returned per enumeration call
+
+/// The parts of the launchpad interface the factory drives directly
+#[allow(dead_code)]
+#[contractclient(name = "LaunchpadClient")]
+pub trait LaunchpadInterface {
+    fn initialize(env: Env, admin: Address, xlm_token: Address);
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>);
+    fn pause(env: Env, caller: Address);
+    fn unpause(env: Env);
+    fn emergency_shutdown(env: Env);
+    fn set_fee_config(env: Env, fee_bps: u32, fee_recipient: Address);
+    fn set_trade_limits(env: Env, limits: TradeLimits);
+    fn configure_governance(env: Env, governance: Governance);
+    fn lock_token_creation(env: Env);
+    #[allow(clippy::too_many_arguments)]
+    fn create_token(
+        env: Env,
+        creator: Address,
+        name: String,
+        symbol: String,
+        total_supply: i128,
+        launch_threshold: i128,
+        launch_threshold_percent: u32,
+        curve: CurveSource,
+        quote_asset: Option<Address>,
+        options: Option<LaunchOptions>,
+    ) -> String;
+}
+
+#[contract]
+pub struct LaunchpadFactory;
+
+#[contractimpl]
+impl LaunchpadFactory {
+    /// Initialize the factory admin, the XLM asset contract and the launchpad wasm to deploy
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        xlm_token: Address,
+        wasm_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        if storage::has_admin(&env) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        admin.require_auth();
+        storage::set_admin(&env, &admin);
+        storage::set_xlm_token(&env, &xlm_token);
+        storage::set_wasm_hash(&env, &wasm_hash);
+
+        Ok(())
+    }
+
+    /// Set the launchpad wasm deployed for new launches (admin only)
+    ///
+    /// Existing instances keep their code until upgraded with `upgrade_instance`.
+    pub fn set_instance_wasm(env: Env, wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+        storage::set_wasm_hash(&env, &wasm_hash);
+
+        Ok(())
+    }
+
+    /// Upgrade a single token's launchpad instance (admin only)
+    pub fn upgrade_instance(
+        env: Env,
+        token_id: String,
+        wasm_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        Self::instance_client(&env, token_id)?.upgrade(&wasm_hash);
+
+        Ok(())
+    }
+
+    /// Pause trading on a token's launchpad instance (admin only)
+    pub fn pause_instance(env: Env, token_id: String) -> Result<(), ContractError> {
+        Self::instance_client(&env, token_id)?.pause(&env.current_contract_address());
+
+        Ok(())
+    }
+
+    /// Resume trading on a token's launchpad instance (admin only)
+    pub fn unpause_instance(env: Env, token_id: String) -> Result<(), ContractError> {
+        Self::instance_client(&env, token_id)?.unpause();
+
+        Ok(())
+    }
+
+    /// Shut down a token's launchpad instance so holders can claim refunds (admin only)
+    pub fn shutdown_instance(env: Env, token_id: String) -> Result<(), ContractError> {
+        Self::instance_client(&env, token_id)?.emergency_shutdown();
+
+        Ok(())
+    }
+
+    /// Set the platform fee charged on a token's launchpad instance (admin only)
+    pub fn set_instance_fee_config(
+        env: Env,
+        token_id: String,
+        fee_bps: u32,
+        fee_recipient: Address,
+    ) -> Result<(), ContractError> {
+        Self::instance_client(&env, token_id)?.set_fee_config(&fee_bps, &fee_recipient);
+
+        Ok(())
+    }
+
+    /// Set the price impact limits on a token's launchpad instance (admin only)
+    pub fn set_instance_trade_limits(
+        env: Env,
+        token_id: String,
+        limits: TradeLimits,
+    ) -> Result<(), ContractError> {
+        Self::instance_client(&env, token_id)?.set_trade_limits(&limits);
+
+        Ok(())
+    }
+
+    /// Hand a token's launchpad instance over to an M-of-N admin set (admin only)
+    ///
+    /// The factory loses its admin powers over the instance, including
+    /// `upgrade_instance` and the other passthroughs.
+    pub fn configure_instance_governance(
+        env: Env,
+        token_id: String,
+        governance: Governance,
+    ) -> Result<(), ContractError> {
+        Self::instance_client(&env, token_id)?.configure_governance(&governance);
+
+        Ok(())
+    }
+
+    /// Deploy a dedicated launchpad instance and create the creator's token on it
+    ///
+    /// The arguments are those of the launchpad's `create_token` and are passed
+    /// to the instance unchanged, so the instance validates them. The factory is
+    /// the instance admin and forwards the factory admin's calls to it. Token
+    /// creation on the instance is then locked, so it only ever holds this
+    /// token's reserve and state.
+    #[allow(clippy::too_many_arguments)]
+    pub fn deploy_launch(
+        env: Env,
+        creator: Address,
+        name: String,
+        symbol: String,
+        total_supply: i128,
+        launch_threshold: i128,
+        launch_threshold_percent: u32,
+        curve: CurveSource,
+        quote_asset: Option<Address>,
+        options: Option<LaunchOptions>,
+    ) -> Result<LaunchInstance, ContractError> {
+        creator.require_auth();
+
+        let wasm_hash = storage::get_wasm_hash(&env).ok_or(ContractError::NotInitialized)?;
+        let xlm_token = storage::get_xlm_token(&env).ok_or(ContractError::NotInitialized)?;
+
+        let instance = env.deployer()
+            .with_current_contract(Self::instance_salt(&env, storage::get_instance_count(&env)))
+            .deploy_v2(wasm_hash, ());
+        let client = LaunchpadClient::new(&env, &instance);
+        client.initialize(&env.current_contract_address(), &xlm_token);
+
+        let token_id = client.create_token(
+            &creator,
+            &name,
+            &symbol,
+            &total_supply,
+            &launch_threshold,
+            &launch_threshold_percent,
+            &curve,
+            &quote_asset,
+            &options,
+        );
+        client.lock_token_creation();
+        if storage::get_token_index(&env, &token_id).is_some() {
+            return Err(ContractError::TokenExists);
+        }
+
+        let launch = LaunchInstance {
+            token_id,
+            instance,
+            creator,
+            deployed_ledger: env.ledger().sequence(),
+        };
+        storage::add_instance(&env, &launch);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("deployed"),),
+            (launch.token_id.clone(), launch.instance.clone(), launch.creator.clone())
+        );
+
+        Ok(launch)
+    }
+
+    /// Get the number of deployed instances
+    pub fn get_instance_count(env: Env) -> u32 {
+        storage::get_instance_count(&env)
+    }
+
+    /// Get up to `limit` instances in deployment order, starting at `start`
+    pub fn get_instances(env: Env, start: u32, limit: u32) -> Vec<LaunchInstance> {
+        let end = storage::get_instance_count(&env)
+            .min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+
+        let mut instances = Vec::new(&env);
+        for index in start..end {
+            if let Some(launch) = storage::get_instance(&env, index) {
+                instances.push_back(launch);
+            }
+        }
+
+        instances
+    }
+
+    /// Get the instance holding a token
+    pub fn get_token_instance(env: Env, token_id: String) -> Result<LaunchInstance, ContractError> {
+        storage::get_token_index(&env, &token_id)
+            .and_then(|index| storage::get_instance(&env, index))
+            .ok_or(ContractError::TokenNotFound)
+    }
+
+    /// Get the launchpad wasm deployed for new launches
+    pub fn get_instance_wasm(env: Env) -> Option<BytesN<32>> {
+        storage::get_wasm_hash(&env)
+    }
+}
+
+impl LaunchpadFactory {
+    fn require_admin(env: &Env) -> Result<Address, ContractError> {
+        let admin = storage::get_admin(env).ok_or(ContractError::NotInitialized)?;
+        admin.require_auth();
+
+        Ok(admin)
+    }
+
+    /// Client for a token's instance, once the factory admin has authorized the call
+    fn instance_client<'a>(env: &Env, token_id: String) -> Result<LaunchpadClient<'a>, ContractError> {
+        Self::require_admin(env)?;
+
+        let launch = Self::get_token_instance(env.clone(), token_id)?;
+        Ok(LaunchpadClient::new(env, &launch.instance))
+    }
+
+    fn instance_salt(env: &Env, index: u32) -> BytesN<32> {
+        let mut salt = [0u8; 32];
+        salt[28..].copy_from_slice(&index.to_be_bytes());
+        BytesN::from_array(env, &salt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, vec};
+    use launchpad_types::CurveType;
+    use crate::types::CurveParameters;
+
+    // Build the launchpad for wasm32v1-none in this workspace first
+    #[allow(clippy::too_many_arguments)]
+    mod launchpad {
+        soroban_sdk::contractimport!(
+            file = "../target/wasm32v1-none/release/stellar_pump_launchpad.wasm"
+        );
+    }
+
+    fn setup_factory<'a>(env: &Env) -> (LaunchpadFactoryClient<'a>, Address) {
+        env.mock_all_auths();
+
+        let client = LaunchpadFactoryClient::new(env, &env.register(LaunchpadFactory, ()));
+        let admin = Address::generate(env);
+        client.initialize(&admin, &Address::generate(env), &BytesN::from_array(env, &[1; 32]));
+
+        (client, admin)
+    }
+
+    #[test]
+    fn test_initialize_and_wasm_hash() {
+        let env = Env::default();
+        let (client, admin) = setup_factory(&env);
+
+        let result = client.try_initialize(&admin, &Address::generate(&env), &BytesN::from_array(&env, &[1; 32]));
+        assert_eq!(result, Err(Ok(ContractError::AlreadyInitialized)));
+
+        let wasm_hash = BytesN::from_array(&env, &[2; 32]);
+        client.set_instance_wasm(&wasm_hash);
+        assert_eq!(client.get_instance_wasm(), Some(wasm_hash));
+    }
+
+    #[test]
+    fn test_empty_registry() {
+        let env = Env::default();
+        let (client, _) = setup_factory(&env);
+
+        assert_eq!(client.get_instance_count(), 0);
+        assert_eq!(client.get_instances(&0, &10).len(), 0);
+        assert_eq!(
+            client.try_get_token_instance(&String::from_str(&env, "NONE")),
+            Err(Ok(ContractError::TokenNotFound))
+        );
+    }
+
+    fn deploy_wasm_launch<'a>(env: &Env) -> (LaunchpadFactoryClient<'a>, LaunchInstance) {
+        env.mock_all_auths();
+        // The launchpad wasm is larger than the default test budget and code size limit allow
+        env.cost_estimate().budget().reset_unlimited();
+        env.cost_estimate().disable_resource_limits();
+
+        let client = LaunchpadFactoryClient::new(env, &env.register(LaunchpadFactory, ()));
+        let wasm_hash = env.deployer().upload_contract_wasm(launchpad::WASM);
+        client.initialize(&Address::generate(env), &Address::generate(env), &wasm_hash);
+
+        let creator = Address::generate(env);
+        let curve = CurveParameters {
+            curve_type: CurveType::Linear,
+            base_price: 1000,
+            price_multiplier: 9000,
+        };
+        let launch = client.deploy_launch(
+            &creator,
+            &String::from_str(env, "Test Token"),
+            &String::from_str(env, "TEST"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(curve),
+            &None,
+            &None,
+        );
+
+        (client, launch)
+    }
+
+    #[test]
+    fn test_deploy_launch() {
+        let env = Env::default();
+        let (client, launch) = deploy_wasm_launch(&env);
+
+        assert_eq!(client.get_instance_count(), 1);
+        assert_eq!(client.get_instances(&0, &10), vec![&env, launch.clone()]);
+        assert_eq!(client.get_token_instance(&launch.token_id), launch);
+
+        // The token lives on its own instance, created for the caller
+        let instance = launchpad::Client::new(&env, &launch.instance);
+        assert_eq!(instance.get_token_info(&launch.token_id).creator, launch.creator);
+
+        // The instance is locked to that one token
+        assert!(instance.is_creation_locked());
+        let result = instance.try_create_token(
+            &launch.creator,
+            &String::from_str(&env, "Second Token"),
+            &String::from_str(&env, "NEXT"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &launchpad::CurveSource::Preset(0),
+            &None,
+            &None,
+        );
+        assert_eq!(result, Err(Ok(launchpad::ContractError::CreationLocked)));
+    }
+
+    #[test]
+    fn test_admin_passthroughs() {
+        let env = Env::default();
+        let (client, launch) = deploy_wasm_launch(&env);
+        let instance = launchpad::Client::new(&env, &launch.instance);
+
+        client.pause_instance(&launch.token_id);
+        assert!(instance.is_paused());
+        client.unpause_instance(&launch.token_id);
+        assert!(!instance.is_paused());
+
+        let fee_recipient = Address::generate(&env);
+        client.set_instance_fee_config(&launch.token_id, &100, &fee_recipient);
+        assert_eq!(
+            instance.get_fee_config(),
+            Some(launchpad::FeeConfig { fee_bps: 100, fee_recipient })
+        );
+
+        let limits = TradeLimits { max_trade_impact_bps: Some(500), max_ledger_move_bps: None };
+        client.set_instance_trade_limits(&launch.token_id, &limits);
+        assert_eq!(instance.get_trade_limits(&launch.token_id).max_trade_impact_bps, Some(500));
+
+        client.shutdown_instance(&launch.token_id);
+        assert!(instance.is_shutdown());
+    }
+
+    #[test]
+    fn test_governance_handover() {
+        let env = Env::default();
+        let (client, launch) = deploy_wasm_launch(&env);
+
+        // Once governance takes over, the factory no longer administers the instance
+        let governance = Governance {
+            admins: vec![&env, Address::generate(&env), Address::generate(&env)],
+            threshold: 2,
+            delay_ledgers: 720,
+        };
+        client.configure_instance_governance(&launch.token_id, &governance);
+        assert!(client.try_unpause_instance(&launch.token_id).is_err());
+    }
+}
@@ -0,0 +1,15 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ContractError {
+    // Authorization errors
+    Unauthorized = 1,
+
+    // State errors
+    TokenNotFound = 13,
+    AlreadyInitialized = 14,
+    NotInitialized = 15,
+    TokenExists = 16,
+}
@@ -0,0 +1,8 @@
+#![no_std]
+
+mod contract;
+mod storage;
+mod types;
+mod errors;
+
+pub use contract::LaunchpadFactory;
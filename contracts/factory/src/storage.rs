@@ -0,0 +1,69 @@
+use soroban_sdk::{Address, BytesN, Env, String};
+use crate::types::LaunchInstance;
+
+// Storage keys
+const ADMIN_KEY: &str = "ADMIN";
+const XLM_TOKEN_KEY: &str = "XLM";
+const WASM_HASH_KEY: &str = "WASM";
+const INSTANCE_COUNT_KEY: &str = "COUNT";
+const INSTANCE_KEY: &str = "INSTANCE";
+const TOKEN_INDEX_KEY: &str = "TOKEN";
+
+/// Check whether the factory has been initialized
+pub fn has_admin(env: &Env) -> bool {
+    env.storage().instance().has(&ADMIN_KEY)
+}
+
+/// Set the factory admin
+pub fn set_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&ADMIN_KEY, admin);
+}
+
+/// Get the factory admin
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&ADMIN_KEY)
+}
+
+/// Set the XLM asset contract passed to new instances
+pub fn set_xlm_token(env: &Env, xlm_token: &Address) {
+    env.storage().instance().set(&XLM_TOKEN_KEY, xlm_token);
+}
+
+/// Get the XLM asset contract passed to new instances
+pub fn get_xlm_token(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&XLM_TOKEN_KEY)
+}
+
+/// Set the launchpad wasm deployed for new instances
+pub fn set_wasm_hash(env: &Env, wasm_hash: &BytesN<32>) {
+    env.storage().instance().set(&WASM_HASH_KEY, wasm_hash);
+}
+
+/// Get the launchpad wasm deployed for new instances
+pub fn get_wasm_hash(env: &Env) -> Option<BytesN<32>> {
+    env.storage().instance().get(&WASM_HASH_KEY)
+}
+
+/// Get the number of deployed instances
+pub fn get_instance_count(env: &Env) -> u32 {
+    env.storage().instance().get(&INSTANCE_COUNT_KEY).unwrap_or(0)
+}
+
+/// Register a deployed instance, returning its index
+pub fn add_instance(env: &Env, instance: &LaunchInstance) -> u32 {
+    let index = get_instance_count(env);
+    env.storage().persistent().set(&(INSTANCE_KEY, index), instance);
+    env.storage().persistent().set(&(TOKEN_INDEX_KEY, instance.token_id.clone()), &index);
+    env.storage().instance().set(&INSTANCE_COUNT_KEY, &(index + 1));
+    index
+}
+
+/// Get a deployed instance by index
+pub fn get_instance(env: &Env, index: u32) -> Option<LaunchInstance> {
+    env.storage().persistent().get(&(INSTANCE_KEY, index))
+}
+
+/// Get the index of the instance holding a token
+pub fn get_token_index(env: &Env, token_id: &String) -> Option<u32> {
+    env.storage().persistent().get(&(TOKEN_INDEX_KEY, token_id.clone()))
+}
@@ -0,0 +1,103 @@
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
+
+pub use launchpad_types::CurveParameters;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LaunchInstance {
+    pub token_id: String,                    // Token created on the instance
+    pub instance: Address,                   // Dedicated launchpad contract for the token
+    pub creator: Address,                    // Account that deployed the launch
+    pub deployed_ledger: u32,                // Ledger the instance was deployed in
+}
+
+/// Mirror of the launchpad's `TradeLimits`, forwarded to instances unchanged
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TradeLimits {
+    pub max_trade_impact_bps: Option<u32>,   // Max price move of a single trade
+    pub max_ledger_move_bps: Option<u32>,    // Max price move within one ledger
+}
+
+/// Mirror of the launchpad's `Governance`, forwarded to instances unchanged
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Governance {
+    pub admins: Vec<Address>,                // Admin set
+    pub threshold: u32,                      // Approvals needed to execute an action
+    pub delay_ledgers: u32,                  // Timelock between proposal and execution
+}
+
+/// Mirror of the launchpad's `CurveSource`, forwarded to instances unchanged
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum CurveSource {
+    Custom(CurveParameters),                 // Creator-supplied curve
+    Preset(u32),                             // Admin-curated preset id
+}
+
+/// Mirror of the launchpad's `LaunchOptions`, forwarded to instances unchanged
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LaunchOptions {
+    pub protection: LaunchProtection,        // Anti-sniping limits
+    pub mode: LaunchMode,                    // How trading opens
+    pub creator_allocation: CreatorAllocation, // Supply reserved for the creator
+    pub launch_deadline_ledgers: u32,        // Ledgers to launch before refunds open, 0 for none
+    pub hook: Option<Address>,               // Contract notified of launch lifecycle events
+    pub hook_trades: bool,                   // Whether the hook is also notified of each trade
+    pub sell_fee: SellFeeSchedule,           // Surcharge on sales by recent buyers
+}
+
+/// Mirror of the launchpad's `LaunchProtection`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LaunchProtection {
+    pub window_ledgers: u32,                 // Ledgers after creation with a per-wallet buy cap
+    pub max_buy_per_wallet: Option<i128>,    // Tokens one wallet may buy inside the window
+    pub max_wallet_percent: Option<u32>,     // % of supply one wallet may hold before launch
+    pub max_dev_buy: Option<i128>,           // Tokens the creator may buy before launch
+}
+
+/// Mirror of the launchpad's `LaunchMode`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum LaunchMode {
+    Open,                                    // Curve trading from creation
+    Presale(PresaleConfig),                  // Allowlisted buyers first
+    Auction(AuctionConfig),                  // Opening batch auction first
+}
+
+/// Mirror of the launchpad's `PresaleConfig`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PresaleConfig {
+    pub merkle_root: BytesN<32>,             // Root of (address, max XLM allocation) leaves
+    pub end_ledger: u32,                     // Ledger public trading opens
+}
+
+/// Mirror of the launchpad's `AuctionConfig`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AuctionConfig {
+    pub duration_ledgers: u32,               // Ledgers commitments are collected for
+    pub max_tokens: i128,                    // Most tokens the auction may sell
+}
+
+/// Mirror of the launchpad's `CreatorAllocation`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CreatorAllocation {
+    pub percent: u32,                        // % of total supply reserved for the creator
+    pub cliff_ledgers: u32,                  // Ledgers after graduation before anything vests
+    pub vesting_ledgers: u32,                // Ledgers after graduation until fully vested
+}
+
+/// Mirror of the launchpad's `SellFeeSchedule`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SellFeeSchedule {
+    pub max_surcharge_bps: u32,              // Added to the platform fee on a sale in the buy ledger, 0 to disable
+    pub decay_ledgers: u32,                  // Holding period over which the surcharge falls to zero
+    pub to_reserve: bool,                    // Keep the surcharge in the reserve instead of paying the fee recipient
+}
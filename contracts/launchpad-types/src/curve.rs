@@ -1,7 +1,60 @@
-use crate::{
-    errors::ContractError,
-    types::{CurveParameters, CurveType},
-};
+use core::{fmt, str::FromStr};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "soroban", soroban_sdk::contracttype)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum CurveType {
+    Linear,
+    Quadratic,
+}
+
+impl CurveType {
+    /// Name used for the curve off-chain
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CurveType::Linear => "linear",
+            CurveType::Quadratic => "quadratic",
+        }
+    }
+}
+
+impl fmt::Display for CurveType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CurveType {
+    type Err = CurveError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "linear" => Ok(CurveType::Linear),
+            "quadratic" => Ok(CurveType::Quadratic),
+            _ => Err(CurveError::InvalidCurveParameters),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "soroban", soroban_sdk::contracttype)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurveParameters {
+    pub curve_type: CurveType,
+    pub base_price: i128,        // Starting price in quote asset stroops
+    pub price_multiplier: i128,  // Price scaling factor
+}
+
+/// Errors from curve math, mapped onto the contract's errors on-chain
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CurveError {
+    InvalidAmount,
+    InvalidCurveParameters,
+    InvalidSupply,
+    CalculationOverflow,
+}
+
 
 pub struct BondingCurve;
 
@@ -12,9 +65,9 @@ impl BondingCurve {
         xlm_amount: i128,
         tokens_sold: i128,
         total_supply: i128,
-    ) -> Result<i128, ContractError> {
+    ) -> Result<i128, CurveError> {
         if xlm_amount <= 0 {
-            return Err(ContractError::InvalidAmount);
+            return Err(CurveError::InvalidAmount);
         }
 
         match curve_params.curve_type {
@@ -36,9 +89,9 @@ impl BondingCurve {
         token_amount: i128,
         tokens_sold: i128,
         total_supply: i128,
-    ) -> Result<i128, ContractError> {
+    ) -> Result<i128, CurveError> {
         if token_amount <= 0 {
-            return Err(ContractError::InvalidAmount);
+            return Err(CurveError::InvalidAmount);
         }
 
         match curve_params.curve_type {
@@ -58,28 +111,80 @@ impl BondingCurve {
         curve_params: &CurveParameters,
         tokens_sold: i128,
         total_supply: i128,
-    ) -> Result<i128, ContractError> {
+    ) -> Result<i128, CurveError> {
         match curve_params.curve_type {
             CurveType::Linear => Self::get_linear_price(curve_params, tokens_sold, total_supply),
             CurveType::Quadratic => Self::get_quadratic_price(curve_params, tokens_sold, total_supply),
         }
     }
 
+    /// Calculate the exact XLM cost of moving the curve from `from_sold` to `to_sold`
+    ///
+    /// Integrates the continuous price function rather than charging the spot
+    /// price for the whole amount.
+    pub fn calculate_cost_between(
+        curve_params: &CurveParameters,
+        from_sold: i128,
+        to_sold: i128,
+        total_supply: i128,
+    ) -> Result<i128, CurveError> {
+        if from_sold < 0 || to_sold < from_sold || to_sold > total_supply {
+            return Err(CurveError::InvalidAmount);
+        }
+
+        let cost_to = Self::cumulative_cost(curve_params, to_sold, total_supply)?;
+        let cost_from = Self::cumulative_cost(curve_params, from_sold, total_supply)?;
+
+        Ok(cost_to - cost_from)
+    }
+
+    /// Calculate the most tokens whose integral cost fits within `xlm_amount`
+    pub fn calculate_tokens_for_cost(
+        curve_params: &CurveParameters,
+        xlm_amount: i128,
+        tokens_sold: i128,
+        total_supply: i128,
+    ) -> Result<i128, CurveError> {
+        if xlm_amount <= 0 {
+            return Err(CurveError::InvalidAmount);
+        }
+
+        // Binary search the integral, which is monotonic in the token amount
+        let mut low = 0i128;
+        let mut high = total_supply - tokens_sold;
+        while low < high {
+            let mid = low + (high - low + 1) / 2;
+            let cost = Self::calculate_cost_between(
+                curve_params,
+                tokens_sold,
+                tokens_sold + mid,
+                total_supply,
+            )?;
+            if cost <= xlm_amount {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        Ok(low)
+    }
+
     /// Validate curve parameters
     pub fn validate_curve_params(
         curve_params: &CurveParameters,
         total_supply: i128,
-    ) -> Result<(), ContractError> {
+    ) -> Result<(), CurveError> {
         if curve_params.base_price <= 0 {
-            return Err(ContractError::InvalidCurveParameters);
+            return Err(CurveError::InvalidCurveParameters);
         }
 
         if curve_params.price_multiplier <= 0 {
-            return Err(ContractError::InvalidCurveParameters);
+            return Err(CurveError::InvalidCurveParameters);
         }
 
         if total_supply <= 0 {
-            return Err(ContractError::InvalidSupply);
+            return Err(CurveError::InvalidSupply);
         }
 
         Ok(())
@@ -87,13 +192,49 @@ impl BondingCurve {
 
     // Private helper functions
 
+    fn cumulative_cost(
+        curve_params: &CurveParameters,
+        tokens_sold: i128,
+        total_supply: i128,
+    ) -> Result<i128, CurveError> {
+        if total_supply <= 0 {
+            return Err(CurveError::InvalidSupply);
+        }
+
+        let base_cost = curve_params.base_price
+            .checked_mul(tokens_sold)
+            .ok_or(CurveError::CalculationOverflow)?;
+
+        // Linear: m * s^2 / (2 * S), Quadratic: m * s^3 / (3 * S^2)
+        let scaled = curve_params.price_multiplier
+            .checked_mul(tokens_sold)
+            .and_then(|v| v.checked_mul(tokens_sold))
+            .ok_or(CurveError::CalculationOverflow)?
+            / total_supply;
+
+        let curve_cost = match curve_params.curve_type {
+            CurveType::Linear => scaled / 2,
+            CurveType::Quadratic => {
+                scaled
+                    .checked_mul(tokens_sold)
+                    .ok_or(CurveError::CalculationOverflow)?
+                    / total_supply
+                    / 3
+            }
+        };
+
+        base_cost
+            .checked_add(curve_cost)
+            .ok_or(CurveError::CalculationOverflow)
+    }
+
     fn get_linear_price(
         curve_params: &CurveParameters,
         tokens_sold: i128,
         total_supply: i128,
-    ) -> Result<i128, ContractError> {
+    ) -> Result<i128, CurveError> {
         if total_supply <= 0 {
-            return Err(ContractError::InvalidSupply);
+            return Err(CurveError::InvalidSupply);
         }
 
         // Linear price: base_price + (tokens_sold / total_supply) * price_multiplier
@@ -102,7 +243,7 @@ impl BondingCurve {
         let current_price = curve_params.base_price + price_increase;
 
         if current_price <= 0 {
-            return Err(ContractError::CalculationOverflow);
+            return Err(CurveError::CalculationOverflow);
         }
 
         Ok(current_price)
@@ -112,9 +253,9 @@ impl BondingCurve {
         curve_params: &CurveParameters,
         tokens_sold: i128,
         total_supply: i128,
-    ) -> Result<i128, ContractError> {
+    ) -> Result<i128, CurveError> {
         if total_supply <= 0 {
-            return Err(ContractError::InvalidSupply);
+            return Err(CurveError::InvalidSupply);
         }
 
         // Quadratic price: base_price + (tokens_sold / total_supply)^2 * price_multiplier
@@ -124,7 +265,7 @@ impl BondingCurve {
         let current_price = curve_params.base_price + price_increase;
 
         if current_price <= 0 {
-            return Err(ContractError::CalculationOverflow);
+            return Err(CurveError::CalculationOverflow);
         }
 
         Ok(current_price)
@@ -221,6 +362,53 @@ mod tests {
         assert_eq!(xlm, 10_000); // 10 * 1000 = 10_000 stroops
     }
 
+    #[test]
+    fn test_cost_between_integral() {
+        let total_supply = 1_000_000i128;
+
+        // Linear: 1000 * 1_000_000 + 9000 * 1_000_000 / 2
+        let cost = BondingCurve::calculate_cost_between(&create_linear_curve(), 0, total_supply, total_supply).unwrap();
+        assert_eq!(cost, 5_500_000_000);
+
+        // Quadratic: 1000 * 1_000_000 + 9000 * 1_000_000 / 3
+        let cost = BondingCurve::calculate_cost_between(&create_quadratic_curve(), 0, total_supply, total_supply).unwrap();
+        assert_eq!(cost, 4_000_000_000);
+
+        // Costs of consecutive ranges add up
+        let curve = create_linear_curve();
+        let first = BondingCurve::calculate_cost_between(&curve, 0, 400_000, total_supply).unwrap();
+        let second = BondingCurve::calculate_cost_between(&curve, 400_000, 1_000_000, total_supply).unwrap();
+        assert_eq!(first + second, 5_500_000_000);
+
+        // Selling past the supply is rejected
+        assert_eq!(
+            BondingCurve::calculate_cost_between(&curve, 0, total_supply + 1, total_supply),
+            Err(CurveError::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_tokens_for_cost() {
+        let curve = create_linear_curve();
+        let total_supply = 1_000_000i128;
+
+        let tokens = BondingCurve::calculate_tokens_for_cost(&curve, 5_500_000_000, 0, total_supply).unwrap();
+        assert_eq!(tokens, total_supply);
+
+        // The result is the largest amount affordable with the given XLM
+        let tokens = BondingCurve::calculate_tokens_for_cost(&curve, 100_000_000, 0, total_supply).unwrap();
+        let cost = BondingCurve::calculate_cost_between(&curve, 0, tokens, total_supply).unwrap();
+        let next_cost = BondingCurve::calculate_cost_between(&curve, 0, tokens + 1, total_supply).unwrap();
+        assert!(cost <= 100_000_000 && next_cost > 100_000_000);
+    }
+
+    #[test]
+    fn test_curve_type_names() {
+        assert_eq!("linear".parse(), Ok(CurveType::Linear));
+        assert_eq!(CurveType::Quadratic.as_str().parse(), Ok(CurveType::Quadratic));
+        assert_eq!("cubic".parse::<CurveType>(), Err(CurveError::InvalidCurveParameters));
+    }
+
     #[test]
     fn test_parameter_validation() {
         let total_supply = 1_000_000i128;
@@ -237,7 +425,7 @@ mod tests {
         };
         assert_eq!(
             BondingCurve::validate_curve_params(&invalid_curve, total_supply),
-            Err(ContractError::InvalidCurveParameters)
+            Err(CurveError::InvalidCurveParameters)
         );
 
         // Test invalid price multiplier
@@ -248,13 +436,13 @@ mod tests {
         };
         assert_eq!(
             BondingCurve::validate_curve_params(&invalid_curve, total_supply),
-            Err(ContractError::InvalidCurveParameters)
+            Err(CurveError::InvalidCurveParameters)
         );
 
         // Test invalid total supply
         assert_eq!(
             BondingCurve::validate_curve_params(&valid_curve, 0),
-            Err(ContractError::InvalidSupply)
+            Err(CurveError::InvalidSupply)
         );
     }
 
@@ -266,18 +454,18 @@ mod tests {
 
         // Test zero XLM amount
         let result = BondingCurve::calculate_tokens_for_xlm(&curve, 0, tokens_sold, total_supply);
-        assert_eq!(result, Err(ContractError::InvalidAmount));
+        assert_eq!(result, Err(CurveError::InvalidAmount));
 
         // Test negative XLM amount
         let result = BondingCurve::calculate_tokens_for_xlm(&curve, -1000, tokens_sold, total_supply);
-        assert_eq!(result, Err(ContractError::InvalidAmount));
+        assert_eq!(result, Err(CurveError::InvalidAmount));
 
         // Test zero token amount
         let result = BondingCurve::calculate_xlm_for_tokens(&curve, 0, tokens_sold, total_supply);
-        assert_eq!(result, Err(ContractError::InvalidAmount));
+        assert_eq!(result, Err(CurveError::InvalidAmount));
 
         // Test negative token amount
         let result = BondingCurve::calculate_xlm_for_tokens(&curve, -10, tokens_sold, total_supply);
-        assert_eq!(result, Err(ContractError::InvalidAmount));
+        assert_eq!(result, Err(CurveError::InvalidAmount));
     }
 }
\ No newline at end of file
@@ -0,0 +1,149 @@
+//! Payloads of the launchpad's trading events
+//!
+//! The contract publishes each event under a single symbol topic with the
+//! payload as a tuple in field order. The types are generic over the string
+//! type `S` and account type `A` so they decode both on-chain and off-chain;
+//! `L` and `H` stand for a list of strings and a 32-byte hash.
+use crate::curve::CurveParameters;
+
+pub const CREATED: &str = "created";
+pub const PURCHASE: &str = "purchase";
+pub const SALE: &str = "sale";
+pub const LAUNCHED: &str = "launched";
+pub const METADATA: &str = "metadata";
+pub const SPONSORED: &str = "sponsored";
+pub const SWAP: &str = "swap";
+pub const SURCHARGE: &str = "surcharge";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenCreated<S, A> {
+    pub token_id: S,
+    pub creator: A,
+    pub name: S,
+    pub symbol: S,
+    pub total_supply: i128,
+    pub quote_asset: A,
+    pub curve_params: CurveParameters,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenPurchased<S, A> {
+    pub token_id: S,
+    pub buyer: A,
+    pub quote_spent: i128,                   // Including the platform fee
+    pub tokens_received: i128,
+}
+
+/// Published after the `purchase` event of a buy paid for by another account
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SponsoredPurchase<S, A> {
+    pub token_id: S,
+    pub payer: A,
+    pub recipient: A,                        // Credited with the tokens
+    pub quote_spent: i128,                   // Including the platform fee
+    pub tokens_received: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenSold<S, A> {
+    pub token_id: S,
+    pub seller: A,
+    pub tokens_sold: i128,
+    pub quote_received: i128,                // After the platform fee and any surcharge
+}
+
+/// Published before the `sale` event of a sale by a recent buyer
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SellSurcharged<S, A> {
+    pub token_id: S,
+    pub seller: A,
+    pub surcharge: i128,
+    pub to_reserve: bool,                    // Kept in the curve reserve rather than sent to the fee recipient
+}
+
+/// Published after the `sale` and `purchase` events of a swap's two legs
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenSwapped<S, A> {
+    pub trader: A,
+    pub from_token: S,
+    pub to_token: S,
+    pub tokens_in: i128,
+    pub quote_amount: i128,                  // Sale proceeds after the fee, all spent on the buy
+    pub tokens_out: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenLaunched<S> {
+    pub token_id: S,
+    pub final_price: i128,
+    pub quote_raised: i128,
+    pub tokens_sold: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenMetadataUpdated<S, L, H> {
+    pub token_id: S,
+    pub image_uri: S,
+    pub description: S,
+    pub website: S,
+    pub socials: L,
+    pub content_hash: H,                     // Hash of the off-chain metadata document
+}
+
+impl<S, A> From<(S, A, S, S, i128, A, CurveParameters)> for TokenCreated<S, A> {
+    fn from(
+        (token_id, creator, name, symbol, total_supply, quote_asset, curve_params): (S, A, S, S, i128, A, CurveParameters),
+    ) -> Self {
+        Self { token_id, creator, name, symbol, total_supply, quote_asset, curve_params }
+    }
+}
+
+impl<S, A> From<(S, A, i128, i128)> for TokenPurchased<S, A> {
+    fn from((token_id, buyer, quote_spent, tokens_received): (S, A, i128, i128)) -> Self {
+        Self { token_id, buyer, quote_spent, tokens_received }
+    }
+}
+
+impl<S, A> From<(S, A, A, i128, i128)> for SponsoredPurchase<S, A> {
+    fn from((token_id, payer, recipient, quote_spent, tokens_received): (S, A, A, i128, i128)) -> Self {
+        Self { token_id, payer, recipient, quote_spent, tokens_received }
+    }
+}
+
+impl<S, A> From<(S, A, i128, i128)> for TokenSold<S, A> {
+    fn from((token_id, seller, tokens_sold, quote_received): (S, A, i128, i128)) -> Self {
+        Self { token_id, seller, tokens_sold, quote_received }
+    }
+}
+
+impl<S, A> From<(S, A, i128, bool)> for SellSurcharged<S, A> {
+    fn from((token_id, seller, surcharge, to_reserve): (S, A, i128, bool)) -> Self {
+        Self { token_id, seller, surcharge, to_reserve }
+    }
+}
+
+impl<S, A> From<(A, S, S, i128, i128, i128)> for TokenSwapped<S, A> {
+    fn from((trader, from_token, to_token, tokens_in, quote_amount, tokens_out): (A, S, S, i128, i128, i128)) -> Self {
+        Self { trader, from_token, to_token, tokens_in, quote_amount, tokens_out }
+    }
+}
+
+impl<S> From<(S, i128, i128, i128)> for TokenLaunched<S> {
+    fn from((token_id, final_price, quote_raised, tokens_sold): (S, i128, i128, i128)) -> Self {
+        Self { token_id, final_price, quote_raised, tokens_sold }
+    }
+}
+
+impl<S, L, H> From<(S, S, S, S, L, H)> for TokenMetadataUpdated<S, L, H> {
+    fn from((token_id, image_uri, description, website, socials, content_hash): (S, S, S, S, L, H)) -> Self {
+        Self { token_id, image_uri, description, website, socials, content_hash }
+    }
+}
@@ -0,0 +1,10 @@
+//! Launchpad types and curve math shared by the contract and off-chain services
+//!
+//! The crate is `no_std`. Enable `soroban` when building contracts and `serde`
+//! for services that decode launchpad data.
+#![no_std]
+
+mod curve;
+pub mod events;
+
+pub use curve::{BondingCurve, CurveError, CurveParameters, CurveType};
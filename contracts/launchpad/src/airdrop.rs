@@ -0,0 +1,52 @@
+use crate::errors::ContractError;
+
+const BITMAP_WORD_BITS: u32 = 128;
+
+pub struct AirdropBitmap;
+
+impl AirdropBitmap {
+    /// Locate a claim index in the bitmap as (word, bit mask)
+    pub fn position(index: u32) -> (u32, u128) {
+        (index / BITMAP_WORD_BITS, 1u128 << (index % BITMAP_WORD_BITS))
+    }
+
+    /// Check whether a claim index is set in its bitmap word
+    pub fn is_claimed(word: u128, index: u32) -> bool {
+        let (_, mask) = Self::position(index);
+        word & mask != 0
+    }
+
+    /// Set a claim index in its bitmap word, rejecting double claims
+    pub fn set_claimed(word: u128, index: u32) -> Result<u128, ContractError> {
+        if Self::is_claimed(word, index) {
+            return Err(ContractError::AlreadyClaimed);
+        }
+
+        let (_, mask) = Self::position(index);
+        Ok(word | mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        assert_eq!(AirdropBitmap::position(0), (0, 1));
+        assert_eq!(AirdropBitmap::position(127), (0, 1u128 << 127));
+        assert_eq!(AirdropBitmap::position(128), (1, 1));
+        assert_eq!(AirdropBitmap::position(300), (2, 1u128 << 44));
+    }
+
+    #[test]
+    fn test_set_claimed() {
+        let word = AirdropBitmap::set_claimed(0, 5).unwrap();
+        assert!(AirdropBitmap::is_claimed(word, 5));
+        assert!(!AirdropBitmap::is_claimed(word, 6));
+
+        // Index 133 shares a bit with index 5 but lives in the next word
+        assert!(!AirdropBitmap::is_claimed(0, 133));
+        assert_eq!(AirdropBitmap::set_claimed(word, 5), Err(ContractError::AlreadyClaimed));
+    }
+}
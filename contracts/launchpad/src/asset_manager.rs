@@ -1,4 +1,4 @@
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{token, Address, Env, String};
 use crate::errors::ContractError;
 
 pub struct AssetManager;
@@ -83,6 +83,28 @@ impl AssetManager {
         Ok(())
     }
 
+    /// Transfer a token's quote asset, such as XLM or USDC, through its asset contract
+    pub fn transfer_quote(
+        env: &Env,
+        quote_asset: &Address,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        if amount < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        // Nothing to move, e.g. a fully used commitment
+        if amount == 0 {
+            return Ok(());
+        }
+
+        token::Client::new(env, quote_asset).transfer(from, to, &amount);
+
+        Ok(())
+    }
+
     /// Get asset information (mock)
     pub fn get_asset_info(
         env: &Env,
@@ -0,0 +1,188 @@
+use launchpad_types::BondingCurve;
+use crate::{
+    errors::ContractError,
+    types::{AuctionConfig, AuctionState, CurveParameters},
+};
+
+pub struct BatchAuction;
+
+impl BatchAuction {
+    /// Validate opening auction settings against the token supply
+    pub fn validate_config(
+        config: &AuctionConfig,
+        total_supply: i128,
+    ) -> Result<(), ContractError> {
+        if config.duration_ledgers == 0 {
+            return Err(ContractError::InvalidAuctionConfig);
+        }
+
+        if config.max_tokens <= 0 || config.max_tokens > total_supply {
+            return Err(ContractError::InvalidAuctionConfig);
+        }
+
+        Ok(())
+    }
+
+    /// Compute the tokens sold and quote asset kept when the auction clears
+    ///
+    /// All commitments are filled along the curve from zero in one batch. If
+    /// they exceed the cost of `max_tokens`, the auction sells exactly
+    /// `max_tokens` and the surplus is refunded pro rata.
+    pub fn clear(
+        curve_params: &CurveParameters,
+        auction: &AuctionState,
+        total_supply: i128,
+    ) -> Result<(i128, i128), ContractError> {
+        if auction.total_committed == 0 {
+            return Ok((0, 0));
+        }
+
+        let max_cost = BondingCurve::calculate_cost_between(
+            curve_params,
+            0,
+            auction.max_tokens,
+            total_supply,
+        )?;
+
+        let tokens = if auction.total_committed >= max_cost {
+            auction.max_tokens
+        } else {
+            BondingCurve::calculate_tokens_for_cost(
+                curve_params,
+                auction.total_committed,
+                0,
+                total_supply,
+            )?
+        };
+
+        let cost = BondingCurve::calculate_cost_between(curve_params, 0, tokens, total_supply)?;
+
+        Ok((tokens, cost))
+    }
+
+    /// Split a participant's commitment into tokens received and quote asset refunded
+    ///
+    /// Shares are rounded on the running total of claimed commitments rather than
+    /// per claim, so once every participant has claimed the tokens and quote asset
+    /// handed out add up to exactly what the auction cleared.
+    pub fn allocation(
+        auction: &AuctionState,
+        commitment: i128,
+    ) -> Result<(i128, i128), ContractError> {
+        if auction.total_committed == 0 {
+            return Ok((0, commitment));
+        }
+
+        let claimed_after = auction.committed_claimed + commitment;
+        let tokens = Self::share(auction.tokens_cleared, claimed_after, auction.total_committed)?
+            - Self::share(auction.tokens_cleared, auction.committed_claimed, auction.total_committed)?;
+        let paid = Self::share(auction.quote_cleared, claimed_after, auction.total_committed)?
+            - Self::share(auction.quote_cleared, auction.committed_claimed, auction.total_committed)?;
+
+        Ok((tokens, commitment - paid))
+    }
+
+    fn share(amount: i128, committed: i128, total_committed: i128) -> Result<i128, ContractError> {
+        amount
+            .checked_mul(committed)
+            .map(|scaled| scaled / total_committed)
+            .ok_or(ContractError::CalculationOverflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CurveType;
+
+    fn create_linear_curve() -> CurveParameters {
+        CurveParameters {
+            curve_type: CurveType::Linear,
+            base_price: 1000,
+            price_multiplier: 9000,
+        }
+    }
+
+    fn create_auction(total_committed: i128) -> AuctionState {
+        AuctionState {
+            end_ledger: 100,
+            max_tokens: 200_000,
+            total_committed,
+            settled: false,
+            tokens_cleared: 0,
+            quote_cleared: 0,
+            clearing_price: 0,
+            committed_claimed: 0,
+        }
+    }
+
+    #[test]
+    fn test_clear_under_subscribed() {
+        let curve = create_linear_curve();
+        let auction = create_auction(100_000_000);
+
+        let (tokens, cost) = BatchAuction::clear(&curve, &auction, 1_000_000).unwrap();
+        assert!(tokens > 0 && tokens < auction.max_tokens);
+        assert!(cost <= auction.total_committed);
+    }
+
+    #[test]
+    fn test_clear_over_subscribed() {
+        let curve = create_linear_curve();
+        let auction = create_auction(10_000_000_000);
+
+        // 200_000 tokens cost 1000 * 200_000 + 9000 * 200_000^2 / 2_000_000
+        let (tokens, cost) = BatchAuction::clear(&curve, &auction, 1_000_000).unwrap();
+        assert_eq!(tokens, 200_000);
+        assert_eq!(cost, 380_000_000);
+    }
+
+    #[test]
+    fn test_allocation_uniform_price() {
+        let mut auction = create_auction(400_000_000);
+        auction.tokens_cleared = 200_000;
+        auction.quote_cleared = 380_000_000;
+
+        // A quarter of the commitments gets a quarter of the tokens and pays a quarter
+        let (tokens, refund) = BatchAuction::allocation(&auction, 100_000_000).unwrap();
+        assert_eq!(tokens, 50_000);
+        assert_eq!(refund, 5_000_000);
+
+        let (tokens, refund) = BatchAuction::allocation(&auction, 300_000_000).unwrap();
+        assert_eq!(tokens, 150_000);
+        assert_eq!(refund, 15_000_000);
+    }
+
+    #[test]
+    fn test_allocation_reconciles() {
+        let mut auction = create_auction(10);
+        auction.tokens_cleared = 1_000;
+        auction.quote_cleared = 7;
+
+        // Three uneven claims that don't divide the cleared amounts
+        let mut tokens_claimed = 0;
+        let mut quote_paid = 0;
+        for commitment in [3, 3, 4] {
+            let (tokens, refund) = BatchAuction::allocation(&auction, commitment).unwrap();
+            assert!(refund >= 0);
+            tokens_claimed += tokens;
+            quote_paid += commitment - refund;
+            auction.committed_claimed += commitment;
+        }
+
+        assert_eq!(tokens_claimed, auction.tokens_cleared);
+        assert_eq!(quote_paid, auction.quote_cleared);
+    }
+
+    #[test]
+    fn test_validate_config() {
+        let config = AuctionConfig { duration_ledgers: 10, max_tokens: 100 };
+        assert!(BatchAuction::validate_config(&config, 1_000).is_ok());
+
+        let config = AuctionConfig { duration_ledgers: 0, max_tokens: 100 };
+        assert_eq!(BatchAuction::validate_config(&config, 1_000), Err(ContractError::InvalidAuctionConfig));
+
+        let config = AuctionConfig { duration_ledgers: 10, max_tokens: 1_001 };
+        assert_eq!(BatchAuction::validate_config(&config, 1_000), Err(ContractError::InvalidAuctionConfig));
+    }
+}
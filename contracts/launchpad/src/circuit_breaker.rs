@@ -0,0 +1,117 @@
+use crate::{
+    errors::ContractError,
+    types::TradeLimits,
+};
+
+pub struct CircuitBreaker;
+
+impl CircuitBreaker {
+    /// Validate trade limit settings
+    pub fn validate_limits(limits: &TradeLimits) -> Result<(), ContractError> {
+        if limits.max_trade_impact_bps == Some(0) || limits.max_ledger_move_bps == Some(0) {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        Ok(())
+    }
+
+    /// Price change between two prices in basis points
+    pub fn price_move_bps(from_price: i128, to_price: i128) -> Result<i128, ContractError> {
+        if from_price <= 0 {
+            return Err(ContractError::CalculationOverflow);
+        }
+
+        let change = (to_price - from_price).abs();
+        change
+            .checked_mul(10000)
+            .map(|scaled| scaled / from_price)
+            .ok_or(ContractError::CalculationOverflow)
+    }
+
+    /// Check a trade's price impact and the token's move within the ledger
+    ///
+    /// `ledger_open_price` is the price before the first trade of the current ledger.
+    pub fn check_trade(
+        limits: &TradeLimits,
+        price_before: i128,
+        price_after: i128,
+        ledger_open_price: i128,
+    ) -> Result<(), ContractError> {
+        if let Some(max_impact) = limits.max_trade_impact_bps {
+            if Self::price_move_bps(price_before, price_after)? > max_impact as i128 {
+                return Err(ContractError::PriceImpactExceeded);
+            }
+        }
+
+        if let Some(max_move) = limits.max_ledger_move_bps {
+            if Self::price_move_bps(ledger_open_price, price_after)? > max_move as i128 {
+                return Err(ContractError::LedgerMoveExceeded);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_limits() -> TradeLimits {
+        TradeLimits {
+            max_trade_impact_bps: Some(500),  // 5% per trade
+            max_ledger_move_bps: Some(1000),  // 10% per ledger
+        }
+    }
+
+    #[test]
+    fn test_price_move_bps() {
+        assert_eq!(CircuitBreaker::price_move_bps(1000, 1000).unwrap(), 0);
+        assert_eq!(CircuitBreaker::price_move_bps(1000, 1050).unwrap(), 500);
+        assert_eq!(CircuitBreaker::price_move_bps(1000, 900).unwrap(), 1000);
+        assert_eq!(
+            CircuitBreaker::price_move_bps(0, 1000),
+            Err(ContractError::CalculationOverflow)
+        );
+    }
+
+    #[test]
+    fn test_trade_impact_limit() {
+        let limits = create_limits();
+
+        assert!(CircuitBreaker::check_trade(&limits, 1000, 1050, 1000).is_ok());
+        assert_eq!(
+            CircuitBreaker::check_trade(&limits, 1000, 1051, 1000),
+            Err(ContractError::PriceImpactExceeded)
+        );
+    }
+
+    #[test]
+    fn test_ledger_move_limit() {
+        let limits = create_limits();
+
+        // Each trade is within 5% but together they move the price more than 10%
+        assert!(CircuitBreaker::check_trade(&limits, 1050, 1100, 1000).is_ok());
+        assert_eq!(
+            CircuitBreaker::check_trade(&limits, 1100, 1150, 1000),
+            Err(ContractError::LedgerMoveExceeded)
+        );
+    }
+
+    #[test]
+    fn test_disabled_limits() {
+        let limits = TradeLimits {
+            max_trade_impact_bps: None,
+            max_ledger_move_bps: None,
+        };
+
+        assert!(CircuitBreaker::check_trade(&limits, 1000, 10000, 1000).is_ok());
+        assert_eq!(
+            CircuitBreaker::validate_limits(&TradeLimits {
+                max_trade_impact_bps: Some(0),
+                max_ledger_move_bps: None,
+            }),
+            Err(ContractError::InvalidAmount)
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, String};
+use crate::errors::ContractError;
+
+const MIN_REVEAL_DELAY_LEDGERS: u32 = 1;   // Reveal in a later ledger than the commit
+const REVEAL_WINDOW_LEDGERS: u32 = 120;    // Approximately 10 minutes
+
+pub struct CommitReveal;
+
+impl CommitReveal {
+    /// Hash the hidden order details a buyer commits to
+    ///
+    /// The buyer and token are part of the preimage, so a copied hash can't be
+    /// revealed by anyone else or against another token.
+    pub fn commitment_hash(
+        env: &Env,
+        buyer: &Address,
+        token_id: &String,
+        xlm_amount: i128,
+        min_tokens_out: i128,
+        salt: &BytesN<32>,
+    ) -> BytesN<32> {
+        let mut data = buyer.clone().to_xdr(env);
+        data.append(&token_id.clone().to_xdr(env));
+        data.append(&Bytes::from_array(env, &xlm_amount.to_be_bytes()));
+        data.extend_from_array(&min_tokens_out.to_be_bytes());
+        data.append(&salt.clone().into());
+        env.crypto().sha256(&data).to_bytes()
+    }
+
+    /// Check that a commitment made at `commit_ledger` can be revealed now
+    pub fn check_reveal(commit_ledger: u32, current_ledger: u32) -> Result<(), ContractError> {
+        if current_ledger < commit_ledger + MIN_REVEAL_DELAY_LEDGERS {
+            return Err(ContractError::RevealTooEarly);
+        }
+
+        if current_ledger > commit_ledger + REVEAL_WINDOW_LEDGERS {
+            return Err(ContractError::RevealWindowClosed);
+        }
+
+        Ok(())
+    }
+
+    /// Check that an unrevealed commitment has expired and can be reclaimed
+    pub fn check_reclaim(commit_ledger: u32, current_ledger: u32) -> Result<(), ContractError> {
+        if current_ledger <= commit_ledger + REVEAL_WINDOW_LEDGERS {
+            return Err(ContractError::RevealWindowOpen);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn test_commitment_hash() {
+        let env = Env::default();
+        let buyer = Address::generate(&env);
+        let token_id = String::from_str(&env, "TEST");
+        let salt = BytesN::from_array(&env, &[7u8; 32]);
+
+        let hash = CommitReveal::commitment_hash(&env, &buyer, &token_id, 1_000, 10, &salt);
+        assert_eq!(hash, CommitReveal::commitment_hash(&env, &buyer, &token_id, 1_000, 10, &salt));
+
+        // Any change to the order details changes the hash
+        assert_ne!(hash, CommitReveal::commitment_hash(&env, &buyer, &token_id, 1_001, 10, &salt));
+        assert_ne!(hash, CommitReveal::commitment_hash(&env, &buyer, &token_id, 1_000, 11, &salt));
+        let other_salt = BytesN::from_array(&env, &[8u8; 32]);
+        assert_ne!(hash, CommitReveal::commitment_hash(&env, &buyer, &token_id, 1_000, 10, &other_salt));
+
+        // So does a different buyer or token
+        let other_buyer = Address::generate(&env);
+        assert_ne!(hash, CommitReveal::commitment_hash(&env, &other_buyer, &token_id, 1_000, 10, &salt));
+        let other_token = String::from_str(&env, "OTHER");
+        assert_ne!(hash, CommitReveal::commitment_hash(&env, &buyer, &other_token, 1_000, 10, &salt));
+    }
+
+    #[test]
+    fn test_reveal_window() {
+        assert_eq!(CommitReveal::check_reveal(100, 100), Err(ContractError::RevealTooEarly));
+        assert!(CommitReveal::check_reveal(100, 101).is_ok());
+        assert!(CommitReveal::check_reveal(100, 220).is_ok());
+        assert_eq!(CommitReveal::check_reveal(100, 221), Err(ContractError::RevealWindowClosed));
+    }
+
+    #[test]
+    fn test_reclaim_after_window() {
+        assert_eq!(CommitReveal::check_reclaim(100, 220), Err(ContractError::RevealWindowOpen));
+        assert!(CommitReveal::check_reclaim(100, 221).is_ok());
+    }
+}
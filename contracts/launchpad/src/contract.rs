@@ -1,189 +1,3955 @@
-use soroban_sdk::{contract, contractimpl, Address, Env, String};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, String, Vec};
+use launchpad_types::BondingCurve;
 use crate::{
+    airdrop::AirdropBitmap,
+    asset_manager::AssetManager,
+    auction::BatchAuction,
+    circuit_breaker::CircuitBreaker,
+    commit_reveal::CommitReveal,
     errors::ContractError,
-    types::{CurveParameters, TokenInfo, PurchaseResult},
+    fees::PlatformFee,
+    governance::AdminGovernance,
+    hooks::LaunchHooks,
+    keeper::KeeperBounty,
+    launch_protection::LaunchGuard,
+    merkle::MerkleProof,
+    metadata::Metadata,
+    migration::StorageMigration,
+    oracle::PriceOracle,
+    orders::LimitOrders,
+    presets::CurvePresets,
+    referral::ReferralProgram,
+    sell_fee::SellFee,
+    refund::Refund,
+    storage,
+    types::{
+        AdminAction, Airdrop, AuctionClaim, AuctionState, BuyCommitment, CreatorAllocation,
+        CurvePreset, CurveSource, FeeConfig, Governance, LaunchHook, LaunchMode, LaunchOptions, LaunchProtection,
+        LimitOrder, OrderSide,
+        PresaleConfig, PurchaseResult, QueuedAction, SellFeeSchedule, SellResult, TokenInfo, TokenMetadata, TradeLimits,
+    },
+    vesting::Vesting,
 };
 
+/// The fixed side of a curve buy
+enum PurchaseSize {
+    Tokens(i128),                            // Exact tokens out
+    Quote(i128),                             // Quote asset in, after the platform fee
+}
+
 #[contract]
 pub struct LaunchpadContract;
 
 #[contractimpl]
 impl LaunchpadContract {
+    /// Initialize the platform admin and the XLM asset contract used as the default quote asset
+    pub fn initialize(env: Env, admin: Address, xlm_token: Address) -> Result<(), ContractError> {
+        if storage::has_admin(&env) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        admin.require_auth();
+        storage::set_admin(&env, &admin);
+        storage::set_xlm_token(&env, &xlm_token);
+        storage::set_schema_version(&env, storage::SCHEMA_VERSION);
+
+        Ok(())
+    }
+
+    /// Get the storage layout version
+    pub fn get_schema_version(env: Env) -> u32 {
+        storage::get_schema_version(&env)
+    }
+
+    /// Queue tokens stored under the previous schema for `migrate` (admin only)
+    ///
+    /// The previous schema kept no list of tokens, so their ids have to be supplied.
+    /// Returns how many were queued; unknown or already migrated ids are skipped.
+    pub fn queue_legacy_tokens(env: Env, token_ids: Vec<String>) -> Result<u32, ContractError> {
+        Self::require_admin(&env)?;
+
+        let mut queued = 0;
+        for token_id in token_ids.iter() {
+            if StorageMigration::queue_token(&env, &token_id) {
+                queued += 1;
+            }
+        }
+
+        Ok(queued)
+    }
+
+    /// Move up to `batch_size` queued tokens to the current schema (admin only)
+    ///
+    /// Returns how many are left. Trading stays paused until this reaches zero.
+    pub fn migrate(env: Env, batch_size: u32) -> Result<u32, ContractError> {
+        if batch_size == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        Self::require_admin(&env)?;
+
+        if !StorageMigration::is_pending(&env) {
+            return Ok(0);
+        }
+
+        let quote_asset = storage::get_xlm_token(&env).ok_or(ContractError::NotInitialized)?;
+        for _ in 0..batch_size {
+            let Some(token_id) = storage::pop_legacy_token(&env) else {
+                break;
+            };
+            StorageMigration::migrate_token(&env, &token_id, &quote_asset);
+        }
+
+        let (head, tail) = storage::get_migration_queue(&env);
+        if head < tail {
+            return Ok(tail - head);
+        }
+
+        StorageMigration::finish(&env);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("migrated"),),
+            (storage::SCHEMA_VERSION, tail)
+        );
+
+        Ok(0)
+    }
+
+    /// Permanently stop all trading so holders of tokens that have not graduated can withdraw (admin only)
+    pub fn emergency_shutdown(env: Env) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+        Self::apply_action(&env, AdminAction::Shutdown)
+    }
+
+    /// Check whether the platform has been shut down
+    pub fn is_shutdown(env: Env) -> bool {
+        storage::is_shutdown(&env)
+    }
+
+    /// Permanently stop new tokens from being created, e.g. on a single-token instance (admin only)
+    pub fn lock_token_creation(env: Env) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+        Self::apply_action(&env, AdminAction::LockTokenCreation)
+    }
+
+    /// Check whether token creation has been locked
+    pub fn is_creation_locked(env: Env) -> bool {
+        storage::is_creation_locked(&env)
+    }
+
+    /// Pause trading immediately (any single admin, exempt from the timelock)
+    pub fn pause(env: Env, caller: Address) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let is_admin = match storage::get_governance(&env) {
+            Some(governance) => AdminGovernance::is_admin(&governance, &caller),
+            None => storage::get_admin(&env) == Some(caller.clone()),
+        };
+        if !is_admin {
+            return Err(ContractError::Unauthorized);
+        }
+
+        storage::set_paused(&env, true);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("paused"),),
+            (caller, env.ledger().sequence())
+        );
+
+        Ok(())
+    }
+
+    /// Resume trading after a pause (admin only)
+    pub fn unpause(env: Env) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+        Self::apply_action(&env, AdminAction::Unpause)
+    }
+
+    /// Check whether trading is paused
+    pub fn is_paused(env: Env) -> bool {
+        storage::is_paused(&env)
+    }
+
+    /// Replace the contract code (admin only)
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+        Self::apply_action(&env, AdminAction::Upgrade(new_wasm_hash))
+    }
+
+    /// Hand admin rights to an M-of-N admin set with a timelock (single admin, once)
+    ///
+    /// Afterwards every admin operation except `pause` goes through
+    /// `propose_action`, `approve_action` and `execute_action`.
+    pub fn configure_governance(env: Env, governance: Governance) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+        Self::apply_action(&env, AdminAction::SetGovernance(governance))
+    }
+
+    /// Get the M-of-N admin set
+    pub fn get_governance(env: Env) -> Option<Governance> {
+        storage::get_governance(&env)
+    }
+
+    /// Queue an admin action behind the timelock, counting the proposer's approval
+    pub fn propose_action(
+        env: Env,
+        proposer: Address,
+        action: AdminAction,
+    ) -> Result<u32, ContractError> {
+        let governance = Self::require_governance_admin(&env, &proposer)?;
+        Self::validate_action(&env, &action)?;
+
+        let action_id = storage::next_action_id(&env);
+        let eta_ledger = env.ledger().sequence() + governance.delay_ledgers;
+        storage::set_queued_action(&env, action_id, &QueuedAction {
+            action,
+            approvals: Vec::from_array(&env, [proposer.clone()]),
+            eta_ledger,
+        });
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("proposed"),),
+            (action_id, proposer, eta_ledger)
+        );
+
+        Ok(action_id)
+    }
+
+    /// Approve a queued admin action
+    pub fn approve_action(env: Env, admin: Address, action_id: u32) -> Result<(), ContractError> {
+        Self::require_governance_admin(&env, &admin)?;
+
+        let mut queued = storage::get_queued_action(&env, action_id)
+            .ok_or(ContractError::ActionNotFound)?;
+        AdminGovernance::add_approval(&mut queued.approvals, &admin);
+        storage::set_queued_action(&env, action_id, &queued);
+
+        Ok(())
+    }
+
+    /// Cancel a queued admin action before it executes
+    pub fn cancel_action(env: Env, admin: Address, action_id: u32) -> Result<(), ContractError> {
+        Self::require_governance_admin(&env, &admin)?;
+
+        if storage::get_queued_action(&env, action_id).is_none() {
+            return Err(ContractError::ActionNotFound);
+        }
+        storage::remove_queued_action(&env, action_id);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("cancelled"),),
+            (action_id, admin)
+        );
+
+        Ok(())
+    }
+
+    /// Execute an approved admin action once its timelock has passed (permissionless)
+    pub fn execute_action(env: Env, action_id: u32) -> Result<(), ContractError> {
+        let governance = storage::get_governance(&env).ok_or(ContractError::NotInitialized)?;
+        let queued = storage::get_queued_action(&env, action_id)
+            .ok_or(ContractError::ActionNotFound)?;
+
+        AdminGovernance::check_execute(&governance, &queued, env.ledger().sequence())?;
+
+        storage::remove_queued_action(&env, action_id);
+        Self::apply_action(&env, queued.action)?;
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("executed"),),
+            action_id
+        );
+
+        Ok(())
+    }
+
+    /// Get a queued admin action
+    pub fn get_queued_action(env: Env, action_id: u32) -> Option<QueuedAction> {
+        storage::get_queued_action(&env, action_id)
+    }
+
+    /// Set the platform fee charged on trades (admin only)
+    pub fn set_fee_config(
+        env: Env,
+        fee_bps: u32,
+        fee_recipient: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+        Self::apply_action(&env, AdminAction::SetFeeConfig(FeeConfig { fee_bps, fee_recipient }))
+    }
+
+    /// Get the platform fee configuration
+    pub fn get_fee_config(env: Env) -> Option<FeeConfig> {
+        storage::get_fee_config(&env)
+    }
+
+    /// Set the share of the platform fee paid to referrers (admin only)
+    pub fn set_referral_share(env: Env, share_bps: u32) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+        Self::apply_action(&env, AdminAction::SetReferralShare(share_bps))
+    }
+
+    /// Get a referrer's unclaimed rewards in one quote asset
+    pub fn get_referral_rewards(env: Env, referrer: Address, quote_asset: Address) -> i128 {
+        storage::get_referral_rewards(&env, &referrer, &quote_asset)
+    }
+
+    /// Pay out a referrer's accrued rewards in one quote asset
+    pub fn claim_referral_rewards(
+        env: Env,
+        referrer: Address,
+        quote_asset: Address,
+    ) -> Result<i128, ContractError> {
+        referrer.require_auth();
+
+        let rewards = storage::get_referral_rewards(&env, &referrer, &quote_asset);
+        if rewards <= 0 {
+            return Err(ContractError::NothingToClaim);
+        }
+
+        storage::set_referral_rewards(&env, &referrer, &quote_asset, 0);
+        AssetManager::transfer_quote(&env, &quote_asset, &env.current_contract_address(), &referrer, rewards)?;
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("refclaim"),),
+            (referrer, quote_asset, rewards)
+        );
+
+        Ok(rewards)
+    }
+
+    /// Set the bounty paid to keepers from a token's reserve (admin only)
+    pub fn set_keeper_bounty(env: Env, bounty_bps: u32) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+        Self::apply_action(&env, AdminAction::SetKeeperBounty(bounty_bps))
+    }
+
+    /// Set the platform-wide price impact limits (admin only)
+    pub fn set_trade_limits(env: Env, limits: TradeLimits) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+        Self::apply_action(&env, AdminAction::SetTradeLimits(limits))
+    }
+
+    /// Override or clear the price impact limits for one token (admin only)
+    pub fn set_token_trade_limits(
+        env: Env,
+        token_id: String,
+        limits: Option<TradeLimits>,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+
+        let action = match limits {
+            Some(limits) => AdminAction::SetTokenTradeLimits(token_id, limits),
+            None => AdminAction::ClearTokenTradeLimits(token_id),
+        };
+        Self::apply_action(&env, action)
+    }
+
+    /// Detach a token's launch hook, e.g. one whose notices exhaust the budget (admin only)
+    pub fn clear_token_hook(env: Env, token_id: String) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+        Self::apply_action(&env, AdminAction::ClearTokenHook(token_id))
+    }
+
+    /// Get a token's launch hook
+    pub fn get_launch_hook(env: Env, token_id: String) -> Option<LaunchHook> {
+        storage::get_launch_hook(&env, &token_id)
+    }
+
+    /// Deliver a token's oldest queued hook notice (permissionless)
+    ///
+    /// Returns false once no notices are queued.
+    pub fn notify_hook(env: Env, token_id: String) -> bool {
+        LaunchHooks::deliver_next(&env, &token_id)
+    }
+
+    /// Get the number of hook notices queued for a token
+    pub fn get_pending_hook_notices(env: Env, token_id: String) -> u32 {
+        let (head, tail) = storage::get_hook_queue(&env, &token_id);
+        tail - head
+    }
+
+    /// Get the price impact limits in effect for a token
+    pub fn get_trade_limits(env: Env, token_id: String) -> TradeLimits {
+        storage::get_token_trade_limits(&env, &token_id)
+            .unwrap_or_else(|| storage::get_trade_limits(&env))
+    }
+
+    /// Add, replace or remove a named curve preset creators can launch with (admin only)
+    pub fn set_curve_preset(
+        env: Env,
+        preset_id: u32,
+        preset: Option<CurvePreset>,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env)?;
+
+        let action = match preset {
+            Some(preset) => AdminAction::SetCurvePreset(preset_id, preset),
+            None => AdminAction::RemoveCurvePreset(preset_id),
+        };
+        Self::apply_action(&env, action)
+    }
+
+    /// Get a curve preset
+    pub fn get_curve_preset(env: Env, preset_id: u32) -> Option<CurvePreset> {
+        storage::get_curve_presets(&env).get(preset_id)
+    }
+
+    /// Get all curve presets by id
+    pub fn get_curve_presets(env: Env) -> Map<u32, CurvePreset> {
+        storage::get_curve_presets(&env)
+    }
+
     /// Create a new token with bonding curve
+    ///
+    /// The curve trades against `quote_asset`, any Stellar Asset Contract such as
+    /// USDC, and defaults to XLM. Prices and `launch_threshold` are in that asset.
+    ///
+    /// With a preset `curve`, its supply and thresholds apply to any of those
+    /// arguments left at zero.
     pub fn create_token(
         env: Env,
         creator: Address,
         name: String,
         symbol: String,
         total_supply: i128,
-        launch_threshold_xlm: i128,
+        launch_threshold: i128,
         launch_threshold_percent: u32,
-        curve_params: CurveParameters,
+        curve: CurveSource,
+        quote_asset: Option<Address>,
+        options: Option<LaunchOptions>,
     ) -> Result<String, ContractError> {
         // Authenticate creator
         creator.require_auth();
+        Self::require_not_paused(&env)?;
 
-        // Basic validation
-        if total_supply <= 0 {
-            return Err(ContractError::InvalidSupply);
+        if storage::is_creation_locked(&env) {
+            return Err(ContractError::CreationLocked);
         }
 
-        if launch_threshold_xlm <= 0 && launch_threshold_percent == 0 {
-            return Err(ContractError::InvalidLaunchThreshold);
-        }
+        let (total_supply, launch_threshold, launch_threshold_percent, curve_params) = match curve {
+            CurveSource::Custom(curve_params) => {
+                (total_supply, launch_threshold, launch_threshold_percent, curve_params)
+            }
+            CurveSource::Preset(preset_id) => {
+                let preset = storage::get_curve_presets(&env)
+                    .get(preset_id)
+                    .ok_or(ContractError::InvalidCurveParameters)?;
+                CurvePresets::apply(preset, total_supply, launch_threshold, launch_threshold_percent)
+            }
+        };
+
+        // Basic validation
+        CurvePresets::validate_terms(total_supply, launch_threshold, launch_threshold_percent, &curve_params)?;
+
+        let quote_asset = match quote_asset {
+            Some(asset) => asset,
+            None => storage::get_xlm_token(&env).ok_or(ContractError::NotInitialized)?,
+        };
 
-        if launch_threshold_percent > 100 {
-            return Err(ContractError::InvalidLaunchThreshold);
+        let options = options.unwrap_or(LaunchOptions {
+            protection: LaunchProtection::none(),
+            mode: LaunchMode::Open,
+            creator_allocation: CreatorAllocation::none(),
+            launch_deadline_ledgers: 0,
+            hook: None,
+            hook_trades: false,
+            sell_fee: SellFeeSchedule::none(),
+        });
+        LaunchGuard::validate_protection(&options.protection, total_supply)?;
+        Vesting::validate_allocation(&options.creator_allocation)?;
+        SellFee::validate_schedule(&options.sell_fee)?;
+
+        // The creator's reserve is held back from the curve
+        let creator_reserved = Vesting::reserved_amount(&options.creator_allocation, total_supply)?;
+        let curve_supply = total_supply - creator_reserved;
+
+        match &options.mode {
+            LaunchMode::Open => {}
+            LaunchMode::Presale(presale) => {
+                if presale.end_ledger <= env.ledger().sequence() {
+                    return Err(ContractError::InvalidPresaleConfig);
+                }
+            }
+            LaunchMode::Auction(auction) => BatchAuction::validate_config(auction, curve_supply)?,
         }
 
         // Create a simple token ID using symbol directly
         let token_key = symbol.clone();
+        if storage::has_token(&env, &token_key) {
+            return Err(ContractError::InvalidTokenState);
+        }
+
+        storage::set_oracle(
+            &env,
+            &token_key,
+            &PriceOracle::initialize(&env, env.ledger().timestamp(), curve_params.base_price),
+        );
 
         // Store basic token info (simplified)
-        env.storage().persistent().set(&token_key, &TokenInfo {
+        storage::set_token(&env, &token_key, &TokenInfo {
             name: name.clone(),
             symbol: symbol.clone(),
             total_supply,
             tokens_sold: 0,
-            xlm_raised: 0,
+            quote_asset: quote_asset.clone(),
+            quote_raised: 0,
+            launch_threshold,
+            launch_threshold_percent,
             current_price: curve_params.base_price,
             launch_progress_percent: 0,
             is_launched: false,
+            launch_ledger: 0,
+            transition_executed: false,
+            launch_deadline: if options.launch_deadline_ledgers > 0 {
+                env.ledger().sequence() + options.launch_deadline_ledgers
+            } else {
+                0
+            },
+            refunds_active: false,
+            tokens_refunded: 0,
             creator: creator.clone(),
             creation_time: env.ledger().timestamp(),
+            creation_ledger: env.ledger().sequence(),
+            curve_params: curve_params.clone(),
+            protection: options.protection,
+            creator_allocation: options.creator_allocation,
+            creator_reserved,
+            creator_vested: 0,
+            creator_claimed: 0,
         });
+        storage::add_token(&env, &token_key);
+
+        match &options.mode {
+            LaunchMode::Open => {}
+            LaunchMode::Presale(presale) => storage::set_presale(&env, &token_key, presale),
+            LaunchMode::Auction(auction) => {
+                storage::set_auction(&env, &token_key, &AuctionState {
+                    end_ledger: env.ledger().sequence() + auction.duration_ledgers,
+                    max_tokens: auction.max_tokens,
+                    total_committed: 0,
+                    settled: false,
+                    tokens_cleared: 0,
+                    quote_cleared: 0,
+                    clearing_price: 0,
+                    committed_claimed: 0,
+                });
+            }
+        }
+
+        if let Some(hook) = options.hook {
+            storage::set_launch_hook(&env, &token_key, &Some(LaunchHook {
+                contract: hook,
+                on_trade: options.hook_trades,
+            }));
+        }
+
+        if options.sell_fee.max_surcharge_bps > 0 {
+            storage::set_sell_fee(&env, &token_key, &options.sell_fee);
+        }
 
         // Emit creation event
         env.events().publish(
             (soroban_sdk::symbol_short!("created"),),
-            (token_key.clone(), creator, name, symbol, total_supply)
+            (token_key.clone(), creator, name, symbol, total_supply, quote_asset, curve_params)
         );
 
         Ok(token_key)
     }
 
-    /// Purchase tokens with XLM (simplified version)
+    /// Purchase tokens with the token's quote asset along the bonding curve
+    ///
+    /// The platform fee is taken out of `xlm_amount` and the rest is spent on the curve.
     pub fn buy_tokens(
         env: Env,
         buyer: Address,
         token_id: String,
         xlm_amount: i128,
+        referrer: Option<Address>,
     ) -> Result<PurchaseResult, ContractError> {
         buyer.require_auth();
 
-        if xlm_amount <= 0 {
+        Self::require_public_trading(&env, &token_id)?;
+        Self::register_referral(&env, &buyer, &referrer)?;
+        let quote_asset = Self::quote_asset(&env, &token_id)?;
+        AssetManager::transfer_quote(&env, &quote_asset, &buyer, &env.current_contract_address(), xlm_amount)?;
+
+        Self::execute_purchase(&env, &buyer, token_id, xlm_amount)
+    }
+
+    /// Buy tokens paid for by `payer` and credited to `recipient`, e.g. a gift or custodial buy
+    ///
+    /// Launch protections apply to the recipient as the holder. A referrer the
+    /// recipient already registered earns its fee share; none is registered here.
+    pub fn buy_tokens_for(
+        env: Env,
+        payer: Address,
+        recipient: Address,
+        token_id: String,
+        xlm_amount: i128,
+        min_tokens_out: i128,
+    ) -> Result<PurchaseResult, ContractError> {
+        payer.require_auth();
+
+        Self::require_public_trading(&env, &token_id)?;
+        let quote_asset = Self::quote_asset(&env, &token_id)?;
+        AssetManager::transfer_quote(&env, &quote_asset, &payer, &env.current_contract_address(), xlm_amount)?;
+
+        let result = Self::execute_purchase(&env, &recipient, token_id.clone(), xlm_amount)?;
+        if result.tokens_received < min_tokens_out {
+            return Err(ContractError::MinimumNotMet);
+        }
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("sponsored"),),
+            (token_id, payer, recipient, result.quote_spent, result.tokens_received)
+        );
+
+        Ok(result)
+    }
+
+    /// Purchase an exact number of tokens, paying at most `max_xlm_in` including fees
+    ///
+    /// The platform fee is charged on the total paid in, as in `buy_tokens`, so
+    /// paying the same total through either entrypoint costs the same fee.
+    pub fn buy_exact_tokens(
+        env: Env,
+        buyer: Address,
+        token_id: String,
+        token_amount: i128,
+        max_xlm_in: i128,
+        referrer: Option<Address>,
+    ) -> Result<PurchaseResult, ContractError> {
+        buyer.require_auth();
+
+        if token_amount <= 0 {
             return Err(ContractError::InvalidAmount);
         }
 
-        // Get token info
-        let mut token_info: TokenInfo = env.storage().persistent()
-            .get(&token_id)
-            .ok_or(ContractError::TokenNotFound)?;
+        Self::require_public_trading(&env, &token_id)?;
+        Self::register_referral(&env, &buyer, &referrer)?;
+        let token_info = Self::get_open_token(&env, &token_id)?;
 
-        if token_info.is_launched {
-            return Err(ContractError::AlreadyLaunched);
+        let (token_amount, xlm_cost) = Self::price_purchase(&token_info, PurchaseSize::Tokens(token_amount))?;
+        let xlm_in = PlatformFee::gross_up(xlm_cost, Self::fee_bps(&env))?;
+        let fee = PlatformFee::calculate_fee(xlm_in, Self::fee_bps(&env))?;
+
+        if xlm_in > max_xlm_in {
+            return Err(ContractError::MaximumExceeded);
+        }
+
+        AssetManager::transfer_quote(
+            &env,
+            &token_info.quote_asset,
+            &buyer,
+            &env.current_contract_address(),
+            xlm_in,
+        )?;
+
+        // Fee rounding stays in the reserve, as it does for a buy by amount
+        Self::apply_purchase(&env, &buyer, token_id, token_info, token_amount, xlm_in - fee, fee)
+    }
+
+    /// Sell tokens back to the bonding curve, receiving at least `min_xlm_out` after fees
+    pub fn sell_tokens(
+        env: Env,
+        seller: Address,
+        token_id: String,
+        token_amount: i128,
+        min_xlm_out: i128,
+        referrer: Option<Address>,
+    ) -> Result<SellResult, ContractError> {
+        seller.require_auth();
+
+        Self::require_public_trading(&env, &token_id)?;
+        Self::register_referral(&env, &seller, &referrer)?;
+        let (quote_asset, result) = Self::execute_sale(&env, &seller, &token_id, token_amount)?;
+
+        if result.quote_received < min_xlm_out {
+            return Err(ContractError::MinimumNotMet);
         }
 
-        // Simple linear pricing calculation
-        let tokens_to_receive = xlm_amount / token_info.current_price;
-        
-        if tokens_to_receive <= 0 {
+        AssetManager::transfer_quote(
+            &env,
+            &quote_asset,
+            &env.current_contract_address(),
+            &seller,
+            result.quote_received,
+        )?;
+
+        Ok(result)
+    }
+
+    /// Sell `from_token` and buy `to_token` with the proceeds in one call
+    ///
+    /// Both tokens must trade against the same quote asset, which never leaves the
+    /// contract. The platform fee is charged once, on the sale, and the trader's
+    /// referrer earns its share as on any other trade.
+    pub fn swap(
+        env: Env,
+        trader: Address,
+        from_token: String,
+        to_token: String,
+        amount_in: i128,
+        min_tokens_out: i128,
+        referrer: Option<Address>,
+    ) -> Result<PurchaseResult, ContractError> {
+        trader.require_auth();
+
+        if from_token == to_token {
             return Err(ContractError::InvalidAmount);
         }
 
-        // Update token info
-        token_info.tokens_sold += tokens_to_receive;
-        token_info.xlm_raised += xlm_amount;
-        
-        // Simple price increase
-        token_info.current_price += token_info.current_price / 100; // 1% increase
+        Self::require_public_trading(&env, &from_token)?;
+        Self::require_public_trading(&env, &to_token)?;
+        Self::register_referral(&env, &trader, &referrer)?;
+        let (quote_asset, sale) = Self::execute_sale(&env, &trader, &from_token, amount_in)?;
 
-        // Check launch conditions
-        let launch_triggered = token_info.xlm_raised >= 100_000_000; // 10 XLM threshold
-        if launch_triggered {
-            token_info.is_launched = true;
+        let token_info = Self::get_open_token(&env, &to_token)?;
+        if token_info.quote_asset != quote_asset {
+            return Err(ContractError::InvalidTokenState);
         }
 
-        // Save updated info
-        env.storage().persistent().set(&token_id, &token_info);
+        let (tokens_to_receive, quote_spent) =
+            Self::price_purchase(&token_info, PurchaseSize::Quote(sale.quote_received))?;
+
+        if tokens_to_receive < min_tokens_out {
+            return Err(ContractError::MinimumNotMet);
+        }
+
+        let result = Self::apply_purchase(
+            &env,
+            &trader,
+            to_token.clone(),
+            token_info,
+            tokens_to_receive,
+            quote_spent,
+            0,
+        )?;
 
-        // Emit purchase event
         env.events().publish(
-            (soroban_sdk::symbol_short!("purchase"),),
-            (token_id, buyer, xlm_amount, tokens_to_receive)
+            (soroban_sdk::symbol_short!("swap"),),
+            (trader, from_token, to_token, amount_in, sale.quote_received, result.tokens_received)
         );
 
-        Ok(PurchaseResult {
-            tokens_received: tokens_to_receive,
-            new_price: token_info.current_price,
-            launch_triggered,
-        })
+        Ok(result)
     }
 
-    /// Get current token price
-    pub fn get_current_price(env: Env, token_id: String) -> Result<i128, ContractError> {
-        let token_info: TokenInfo = env.storage().persistent()
-            .get(&token_id)
-            .ok_or(ContractError::TokenNotFound)?;
+    /// Escrow a limit order for keepers to fill once the spot price crosses its trigger
+    ///
+    /// Buys escrow `amount` of the quote asset and sells escrow `amount` tokens.
+    /// Fills credit the owner's registered referrer, so orders take no referrer of their own.
+    pub fn place_order(env: Env, token_id: String, order: LimitOrder) -> Result<u32, ContractError> {
+        order.owner.require_auth();
 
-        Ok(token_info.current_price)
-    }
+        Self::require_public_trading(&env, &token_id)?;
+        let token_info = Self::get_open_token(&env, &token_id)?;
+        LimitOrders::validate_order(&order, env.ledger().sequence())?;
 
-    /// Get token information
-    pub fn get_token_info(env: Env, token_id: String) -> Result<TokenInfo, ContractError> {
-        let token_info: TokenInfo = env.storage().persistent()
-            .get(&token_id)
-            .ok_or(ContractError::TokenNotFound)?;
+        match order.side {
+            OrderSide::Buy => AssetManager::transfer_quote(
+                &env,
+                &token_info.quote_asset,
+                &order.owner,
+                &env.current_contract_address(),
+                order.amount,
+            )?,
+            OrderSide::Sell => {
+                let balance = storage::get_balance(&env, &token_id, &order.owner);
+                if order.amount > balance {
+                    return Err(ContractError::InsufficientFunds);
+                }
 
-        Ok(token_info)
-    }
+                storage::set_balance(&env, &token_id, &order.owner, balance - order.amount);
 
-    /// Get total number of tokens created
-    pub fn get_token_count(env: Env) -> u32 {
-        env.storage().persistent().get(&soroban_sdk::symbol_short!("count")).unwrap_or(0)
+                let escrowed = storage::get_order_escrow(&env, &token_id, &order.owner);
+                storage::set_order_escrow(&env, &token_id, &order.owner, escrowed + order.amount);
+            }
+        }
+
+        let order_id = storage::next_order_id(&env, &token_id);
+        storage::set_limit_order(&env, &token_id, order_id, &order);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("order"),),
+            (token_id, order_id, order.owner, order.side, order.amount, order.trigger_price)
+        );
+
+        Ok(order_id)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
-    use crate::types::{CurveType, CurveParameters};
+    /// Cancel an open limit order and return its escrow (owner only)
+    pub fn cancel_order(env: Env, token_id: String, order_id: u32) -> Result<(), ContractError> {
+        let order = storage::get_limit_order(&env, &token_id, order_id)
+            .ok_or(ContractError::OrderNotFound)?;
+        order.owner.require_auth();
 
-    #[test]
-    fn test_create_token() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, LaunchpadContract);
-        let client = LaunchpadContractClient::new(&env, &contract_id);
+        storage::remove_limit_order(&env, &token_id, order_id);
+        match order.side {
+            OrderSide::Buy => AssetManager::transfer_quote(
+                &env,
+                &Self::quote_asset(&env, &token_id)?,
+                &env.current_contract_address(),
+                &order.owner,
+                order.amount,
+            )?,
+            OrderSide::Sell => {
+                let escrowed = storage::get_order_escrow(&env, &token_id, &order.owner);
+                storage::set_order_escrow(&env, &token_id, &order.owner, escrowed - order.amount);
 
-        let creator = Address::generate(&env);
-        let name = String::from_str(&env, "Test Token");
-        let symbol = String::from_str(&env, "TEST");
-        let total_supply = 1_000_000i128;
-        let launch_threshold_xlm = 100_000_000i128; // 10 XLM
-        let launch_threshold_percent = 80u32;
-        let curve_params = CurveParameters {
-            curve_type: CurveType::Linear,
-            base_price: 1000,
-            price_multiplier: 9000,
-        };
+                let balance = storage::get_balance(&env, &token_id, &order.owner);
+                storage::set_balance(&env, &token_id, &order.owner, balance + order.amount);
+            }
+        }
 
-        let token_id = client.create_token(
-            &creator,
-            &name,
-            &symbol,
-            &total_supply,
-            &launch_threshold_xlm,
-            &launch_threshold_percent,
-            &curve_params,
+        env.events().publish(
+            (soroban_sdk::symbol_short!("ordcancel"),),
+            (token_id, order_id, order.owner)
+        );
+
+        Ok(())
+    }
+
+    /// Fill a triggered limit order at the curve price, paying the executor its fee (permissionless)
+    ///
+    /// Returns the tokens bought or quote received by the order's owner.
+    pub fn execute_order(
+        env: Env,
+        executor: Address,
+        token_id: String,
+        order_id: u32,
+    ) -> Result<i128, ContractError> {
+        executor.require_auth();
+
+        let order = storage::get_limit_order(&env, &token_id, order_id)
+            .ok_or(ContractError::OrderNotFound)?;
+        LimitOrders::check_expiry(&order, env.ledger().sequence())?;
+
+        Self::require_public_trading(&env, &token_id)?;
+        let token_info = Self::get_open_token(&env, &token_id)?;
+        LimitOrders::check_trigger(&order, token_info.current_price)?;
+        storage::remove_limit_order(&env, &token_id, order_id);
+
+        let filled = match order.side {
+            OrderSide::Buy => {
+                let spend = order.amount - order.executor_fee;
+                let result = Self::execute_purchase(&env, &order.owner, token_id.clone(), spend)?;
+                if result.tokens_received < order.min_out {
+                    return Err(ContractError::MinimumNotMet);
+                }
+
+                result.tokens_received
+            }
+            OrderSide::Sell => {
+                // The escrowed tokens go back to the owner for the sale
+                let escrowed = storage::get_order_escrow(&env, &token_id, &order.owner);
+                storage::set_order_escrow(&env, &token_id, &order.owner, escrowed - order.amount);
+                let balance = storage::get_balance(&env, &token_id, &order.owner);
+                storage::set_balance(&env, &token_id, &order.owner, balance + order.amount);
+
+                let (_, sale) = Self::execute_sale(&env, &order.owner, &token_id, order.amount)?;
+                let payout = sale.quote_received - order.executor_fee;
+                if payout < 0 || payout < order.min_out {
+                    return Err(ContractError::MinimumNotMet);
+                }
+
+                AssetManager::transfer_quote(
+                    &env,
+                    &token_info.quote_asset,
+                    &env.current_contract_address(),
+                    &order.owner,
+                    payout,
+                )?;
+                payout
+            }
+        };
+
+        AssetManager::transfer_quote(
+            &env,
+            &token_info.quote_asset,
+            &env.current_contract_address(),
+            &executor,
+            order.executor_fee,
+        )?;
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("filled"),),
+            (token_id, order_id, order.owner, executor, filled)
+        );
+
+        Ok(filled)
+    }
+
+    /// Get an open limit order
+    pub fn get_limit_order(env: Env, token_id: String, order_id: u32) -> Option<LimitOrder> {
+        storage::get_limit_order(&env, &token_id, order_id)
+    }
+
+    /// Purchase tokens during a token's allowlisted presale
+    ///
+    /// `referrer` is registered for the buyer as on `buy_tokens`.
+    pub fn buy_presale(
+        env: Env,
+        buyer: Address,
+        token_id: String,
+        xlm_amount: i128,
+        allocation: i128,
+        proof: Vec<BytesN<32>>,
+        referrer: Option<Address>,
+    ) -> Result<PurchaseResult, ContractError> {
+        buyer.require_auth();
+
+        let presale = storage::get_presale(&env, &token_id)
+            .ok_or(ContractError::InvalidTokenState)?;
+
+        if env.ledger().sequence() >= presale.end_ledger {
+            return Err(ContractError::PresaleEnded);
+        }
+
+        // Prove the buyer's (address, allocation) entry is in the allowlist
+        let leaf = MerkleProof::leaf_hash(&env, &buyer, allocation);
+        if !MerkleProof::verify(&env, &presale.merkle_root, &leaf, &proof) {
+            return Err(ContractError::InvalidProof);
+        }
+
+        let spent = storage::get_presale_spent(&env, &token_id, &buyer);
+        if spent + xlm_amount > allocation {
+            return Err(ContractError::AllocationExceeded);
+        }
+
+        Self::register_referral(&env, &buyer, &referrer)?;
+        let quote_asset = Self::quote_asset(&env, &token_id)?;
+        AssetManager::transfer_quote(&env, &quote_asset, &buyer, &env.current_contract_address(), xlm_amount)?;
+        let result = Self::execute_purchase(&env, &buyer, token_id.clone(), xlm_amount)?;
+        storage::set_presale_spent(&env, &token_id, &buyer, spent + xlm_amount);
+
+        Ok(result)
+    }
+
+    /// Get a token's presale configuration
+    pub fn get_presale(env: Env, token_id: String) -> Option<PresaleConfig> {
+        storage::get_presale(&env, &token_id)
+    }
+
+    /// Lock quote asset behind a hash of hidden buy order details
+    pub fn commit_buy(
+        env: Env,
+        buyer: Address,
+        token_id: String,
+        hash: BytesN<32>,
+        escrowed_xlm: i128,
+    ) -> Result<(), ContractError> {
+        buyer.require_auth();
+
+        if escrowed_xlm <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        Self::require_not_paused(&env)?;
+        let quote_asset = Self::quote_asset(&env, &token_id)?;
+
+        if storage::get_buy_commitment(&env, &buyer, &hash).is_some() {
+            return Err(ContractError::CommitmentExists);
+        }
+
+        AssetManager::transfer_quote(&env, &quote_asset, &buyer, &env.current_contract_address(), escrowed_xlm)?;
+        storage::set_buy_commitment(&env, &buyer, &hash, &BuyCommitment {
+            buyer: buyer.clone(),
+            token_id: token_id.clone(),
+            escrowed_xlm,
+            commit_ledger: env.ledger().sequence(),
+        });
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("buycommit"),),
+            (token_id, buyer, hash, escrowed_xlm)
+        );
+
+        Ok(())
+    }
+
+    /// Reveal a committed buy and execute it at the current curve price
+    ///
+    /// `referrer` is registered for the buyer as on `buy_tokens`.
+    pub fn reveal_buy(
+        env: Env,
+        buyer: Address,
+        token_id: String,
+        xlm_amount: i128,
+        min_tokens_out: i128,
+        salt: BytesN<32>,
+        referrer: Option<Address>,
+    ) -> Result<PurchaseResult, ContractError> {
+        buyer.require_auth();
+
+        let hash = CommitReveal::commitment_hash(&env, &buyer, &token_id, xlm_amount, min_tokens_out, &salt);
+        let commitment = storage::get_buy_commitment(&env, &buyer, &hash)
+            .filter(|c| c.buyer == buyer && c.token_id == token_id)
+            .ok_or(ContractError::CommitmentNotFound)?;
+
+        CommitReveal::check_reveal(commitment.commit_ledger, env.ledger().sequence())?;
+
+        if xlm_amount > commitment.escrowed_xlm {
+            return Err(ContractError::InsufficientFunds);
+        }
+
+        Self::require_public_trading(&env, &token_id)?;
+        Self::register_referral(&env, &buyer, &referrer)?;
+        storage::remove_buy_commitment(&env, &buyer, &hash);
+
+        // The escrow already sits in the contract, so only the excess moves
+        let quote_asset = Self::quote_asset(&env, &token_id)?;
+        let result = Self::execute_purchase(&env, &buyer, token_id, xlm_amount)?;
+        if result.tokens_received < min_tokens_out {
+            return Err(ContractError::MinimumNotMet);
+        }
+
+        AssetManager::transfer_quote(
+            &env,
+            &quote_asset,
+            &env.current_contract_address(),
+            &buyer,
+            commitment.escrowed_xlm - xlm_amount,
+        )?;
+
+        Ok(result)
+    }
+
+    /// Reclaim the escrow of a commitment that was never revealed
+    pub fn reclaim_buy(env: Env, buyer: Address, hash: BytesN<32>) -> Result<i128, ContractError> {
+        buyer.require_auth();
+
+        let commitment = storage::get_buy_commitment(&env, &buyer, &hash)
+            .filter(|c| c.buyer == buyer)
+            .ok_or(ContractError::CommitmentNotFound)?;
+
+        CommitReveal::check_reclaim(commitment.commit_ledger, env.ledger().sequence())?;
+
+        let quote_asset = Self::quote_asset(&env, &commitment.token_id)?;
+        storage::remove_buy_commitment(&env, &buyer, &hash);
+        AssetManager::transfer_quote(
+            &env,
+            &quote_asset,
+            &env.current_contract_address(),
+            &buyer,
+            commitment.escrowed_xlm,
+        )?;
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("reclaim"),),
+            (commitment.token_id, buyer, hash, commitment.escrowed_xlm)
+        );
+
+        Ok(commitment.escrowed_xlm)
+    }
+
+    /// Get a buyer's hidden buy commitment by its hash
+    pub fn get_buy_commitment(env: Env, buyer: Address, hash: BytesN<32>) -> Option<BuyCommitment> {
+        storage::get_buy_commitment(&env, &buyer, &hash).filter(|c| c.buyer == buyer)
+    }
+
+    /// Commit quote asset to a token's opening auction
+    pub fn commit_auction(
+        env: Env,
+        buyer: Address,
+        token_id: String,
+        xlm_amount: i128,
+    ) -> Result<i128, ContractError> {
+        buyer.require_auth();
+
+        if xlm_amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        Self::require_not_paused(&env)?;
+        let mut auction = storage::get_auction(&env, &token_id)
+            .ok_or(ContractError::InvalidTokenState)?;
+
+        if env.ledger().sequence() >= auction.end_ledger {
+            return Err(ContractError::AuctionEnded);
+        }
+
+        let quote_asset = Self::quote_asset(&env, &token_id)?;
+        AssetManager::transfer_quote(&env, &quote_asset, &buyer, &env.current_contract_address(), xlm_amount)?;
+
+        let commitment = storage::get_commitment(&env, &token_id, &buyer) + xlm_amount;
+        storage::set_commitment(&env, &token_id, &buyer, commitment);
+        auction.total_committed += xlm_amount;
+        storage::set_auction(&env, &token_id, &auction);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("commit"),),
+            (token_id, buyer, xlm_amount)
+        );
+
+        Ok(commitment)
+    }
+
+    /// Settle a token's opening auction at a uniform clearing price (permissionless)
+    pub fn settle_auction(env: Env, token_id: String) -> Result<AuctionState, ContractError> {
+        let mut auction = storage::get_auction(&env, &token_id)
+            .ok_or(ContractError::InvalidTokenState)?;
+
+        if auction.settled {
+            return Err(ContractError::InvalidTokenState);
+        }
+
+        if env.ledger().sequence() < auction.end_ledger {
+            return Err(ContractError::AuctionNotEnded);
+        }
+
+        let mut token_info = storage::get_token(&env, &token_id)
+            .ok_or(ContractError::TokenNotFound)?;
+
+        let (tokens_cleared, quote_cleared) = BatchAuction::clear(
+            &token_info.curve_params,
+            &auction,
+            token_info.total_supply,
+        )?;
+
+        auction.settled = true;
+        auction.tokens_cleared = tokens_cleared;
+        auction.quote_cleared = quote_cleared;
+        auction.clearing_price = if tokens_cleared > 0 { quote_cleared / tokens_cleared } else { 0 };
+
+        // Curve trading resumes from where the auction left the curve
+        token_info.tokens_sold += tokens_cleared;
+        token_info.quote_raised += quote_cleared;
+        token_info.current_price = BondingCurve::get_current_price(
+            &token_info.curve_params,
+            token_info.tokens_sold,
+            token_info.total_supply,
+        )?;
+        Self::record_price(&env, &token_id, token_info.current_price)?;
+        Self::update_launch_status(&env, &mut token_info);
+
+        storage::set_token(&env, &token_id, &token_info);
+        storage::set_auction(&env, &token_id, &auction);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("auction"),),
+            (token_id, tokens_cleared, quote_cleared, auction.clearing_price)
+        );
+
+        Ok(auction)
+    }
+
+    /// Deliver a participant's auction tokens and refund unused quote asset
+    pub fn claim_auction(
+        env: Env,
+        buyer: Address,
+        token_id: String,
+    ) -> Result<AuctionClaim, ContractError> {
+        let mut auction = storage::get_auction(&env, &token_id)
+            .ok_or(ContractError::InvalidTokenState)?;
+
+        if !auction.settled {
+            return Err(ContractError::AuctionNotSettled);
+        }
+
+        let commitment = storage::get_commitment(&env, &token_id, &buyer);
+        if commitment <= 0 {
+            return Err(ContractError::NothingToClaim);
+        }
+
+        let (mut tokens_received, mut quote_refunded) = BatchAuction::allocation(&auction, commitment)?;
+        storage::remove_commitment(&env, &token_id, &buyer);
+        auction.committed_claimed += commitment;
+        storage::set_auction(&env, &token_id, &auction);
+
+        // Tokens over the holding cap go back to the curve and their share of the payment is refunded
+        let mut token_info = storage::get_token(&env, &token_id)
+            .ok_or(ContractError::TokenNotFound)?;
+        if let Some(room) = Self::holding_room(&env, &token_id, &token_info, &buyer) {
+            if tokens_received > room {
+                let excess = tokens_received - room;
+                let excess_paid = (commitment - quote_refunded)
+                    .checked_mul(excess)
+                    .map(|scaled| scaled / tokens_received)
+                    .ok_or(ContractError::CalculationOverflow)?;
+
+                token_info.tokens_sold -= excess;
+                token_info.quote_raised -= excess_paid;
+                token_info.current_price = BondingCurve::get_current_price(
+                    &token_info.curve_params,
+                    token_info.tokens_sold,
+                    token_info.total_supply,
+                )?;
+                Self::record_price(&env, &token_id, token_info.current_price)?;
+                storage::set_token(&env, &token_id, &token_info);
+
+                tokens_received = room;
+                quote_refunded += excess_paid;
+            }
+        }
+
+        let balance = storage::get_balance(&env, &token_id, &buyer);
+        storage::set_balance(&env, &token_id, &buyer, balance + tokens_received);
+        let quote_asset = Self::quote_asset(&env, &token_id)?;
+        AssetManager::transfer_quote(&env, &quote_asset, &env.current_contract_address(), &buyer, quote_refunded)?;
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("purchase"),),
+            (token_id, buyer, commitment - quote_refunded, tokens_received)
+        );
+
+        Ok(AuctionClaim { tokens_received, quote_refunded })
+    }
+
+    /// Get a token's opening auction state
+    pub fn get_auction(env: Env, token_id: String) -> Option<AuctionState> {
+        storage::get_auction(&env, &token_id)
+    }
+
+    /// Get current token price
+    pub fn get_current_price(env: Env, token_id: String) -> Result<i128, ContractError> {
+        let token_info = storage::get_token(&env, &token_id)
+            .ok_or(ContractError::TokenNotFound)?;
+
+        Ok(token_info.current_price)
+    }
+
+    /// Get a token's time-weighted average price over the last `window_seconds`
+    pub fn get_twap(env: Env, token_id: String, window_seconds: u64) -> Result<i128, ContractError> {
+        let oracle = storage::get_oracle(&env, &token_id).ok_or(ContractError::TokenNotFound)?;
+
+        PriceOracle::twap(&oracle, env.ledger().timestamp(), window_seconds)
+    }
+
+    /// Get token information
+    pub fn get_token_info(env: Env, token_id: String) -> Result<TokenInfo, ContractError> {
+        let mut token_info = storage::get_token(&env, &token_id)
+            .ok_or(ContractError::TokenNotFound)?;
+        token_info.creator_vested = Self::creator_vested(&env, &token_info)?;
+
+        Ok(token_info)
+    }
+
+    /// Set a token's image, description and links (creator only, frozen at graduation)
+    pub fn set_token_metadata(
+        env: Env,
+        token_id: String,
+        metadata: TokenMetadata,
+    ) -> Result<(), ContractError> {
+        let token_info = storage::get_token(&env, &token_id)
+            .ok_or(ContractError::TokenNotFound)?;
+        token_info.creator.require_auth();
+
+        Metadata::check_editable(&token_info)?;
+        Metadata::validate(&metadata)?;
+        storage::set_metadata(&env, &token_id, &metadata);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("metadata"),),
+            (
+                token_id,
+                metadata.image_uri,
+                metadata.description,
+                metadata.website,
+                metadata.socials,
+                metadata.content_hash,
+            )
+        );
+
+        Ok(())
+    }
+
+    /// Get a token's metadata, if the creator has set any
+    pub fn get_token_metadata(env: Env, token_id: String) -> Option<TokenMetadata> {
+        storage::get_metadata(&env, &token_id)
+    }
+
+    /// Get a token's sell surcharge schedule, if it opted into one
+    pub fn get_sell_fee(env: Env, token_id: String) -> Option<SellFeeSchedule> {
+        storage::get_sell_fee(&env, &token_id)
+    }
+
+    /// Get the ledger a holder's surcharge decay is measured from
+    pub fn get_holding_ledger(env: Env, token_id: String, holder: Address) -> Option<u32> {
+        storage::get_holding_ledger(&env, &token_id, &holder)
+    }
+
+    /// Pay out the creator's vested allocation after graduation
+    pub fn claim_vested(env: Env, token_id: String) -> Result<i128, ContractError> {
+        let mut token_info = storage::get_token(&env, &token_id)
+            .ok_or(ContractError::TokenNotFound)?;
+
+        token_info.creator.require_auth();
+
+        if !token_info.is_launched {
+            return Err(ContractError::NotLaunched);
+        }
+
+        let claimable = Self::creator_vested(&env, &token_info)? - token_info.creator_claimed;
+        if claimable <= 0 {
+            return Err(ContractError::NothingToClaim);
+        }
+
+        token_info.creator_claimed += claimable;
+        storage::set_token(&env, &token_id, &token_info);
+
+        let creator = token_info.creator;
+        let balance = storage::get_balance(&env, &token_id, &creator);
+        storage::set_balance(&env, &token_id, &creator, balance + claimable);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("vested"),),
+            (token_id, creator, claimable)
+        );
+
+        Ok(claimable)
+    }
+
+    /// Graduate a token whose curve has completed, paying the caller a bounty (permissionless)
+    pub fn execute_launch_transition(
+        env: Env,
+        keeper: Address,
+        token_id: String,
+    ) -> Result<i128, ContractError> {
+        keeper.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let mut token_info = storage::get_token(&env, &token_id)
+            .ok_or(ContractError::TokenNotFound)?;
+
+        if !token_info.is_launched {
+            return Err(ContractError::NotLaunched);
+        }
+
+        if token_info.transition_executed {
+            return Err(ContractError::InvalidTokenState);
+        }
+
+        token_info.transition_executed = true;
+        let bounty = Self::pay_keeper_bounty(&env, &token_id, &mut token_info, &keeper)?;
+        storage::set_token(&env, &token_id, &token_info);
+
+        LaunchHooks::queue_graduation(&env, &token_id, &token_info);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("launched"),),
+            (token_id, token_info.current_price, token_info.quote_raised, token_info.tokens_sold)
+        );
+
+        Ok(bounty)
+    }
+
+    /// Open refunds for a token that missed its launch deadline, paying the caller a bounty (permissionless)
+    pub fn activate_refunds(
+        env: Env,
+        keeper: Address,
+        token_id: String,
+    ) -> Result<i128, ContractError> {
+        keeper.require_auth();
+
+        let mut token_info = Self::get_open_token(&env, &token_id)?;
+
+        if token_info.launch_deadline == 0 || env.ledger().sequence() < token_info.launch_deadline {
+            return Err(ContractError::InvalidTokenState);
+        }
+
+        token_info.refunds_active = true;
+        let bounty = Self::pay_keeper_bounty(&env, &token_id, &mut token_info, &keeper)?;
+        storage::set_token(&env, &token_id, &token_info);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("refunds"),),
+            (token_id, token_info.quote_raised, token_info.tokens_sold)
+        );
+
+        Ok(bounty)
+    }
+
+    /// Surrender a holder's tokens for their pro-rata share of the reserve
+    ///
+    /// Open once a token's refunds are activated, or for any token whose curve had not
+    /// completed when the platform was shut down. Graduation is the curve completing
+    /// (`is_launched`), whether or not a keeper has executed the launch transition yet.
+    pub fn claim_refund(env: Env, holder: Address, token_id: String) -> Result<i128, ContractError> {
+        holder.require_auth();
+
+        let mut token_info = storage::get_token(&env, &token_id)
+            .ok_or(ContractError::TokenNotFound)?;
+
+        let shutdown_exit = storage::is_shutdown(&env) && !token_info.is_launched;
+        if !token_info.refunds_active && !shutdown_exit {
+            return Err(ContractError::InvalidTokenState);
+        }
+
+        let balance = storage::get_balance(&env, &token_id, &holder);
+        if balance <= 0 {
+            return Err(ContractError::NothingToClaim);
+        }
+
+        // Creator tokens only vest after graduation, so every balance here was bought on the curve
+        let outstanding = token_info.tokens_sold - token_info.tokens_refunded;
+        let refund = Refund::pro_rata_share(token_info.quote_raised, balance, outstanding)?;
+        token_info.quote_raised -= refund;
+        token_info.tokens_refunded += balance;
+        storage::set_token(&env, &token_id, &token_info);
+        storage::set_balance(&env, &token_id, &holder, 0);
+
+        AssetManager::transfer_quote(
+            &env,
+            &token_info.quote_asset,
+            &env.current_contract_address(),
+            &holder,
+            refund,
+        )?;
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("refund"),),
+            (token_id, holder, balance, refund)
+        );
+
+        Ok(refund)
+    }
+
+    /// Fund an airdrop from the creator's tokens, claimable against a Merkle root
+    pub fn create_airdrop(
+        env: Env,
+        token_id: String,
+        total_amount: i128,
+        merkle_root: BytesN<32>,
+        expiry_ledger: u32,
+    ) -> Result<u32, ContractError> {
+        let token_info = storage::get_token(&env, &token_id)
+            .ok_or(ContractError::TokenNotFound)?;
+
+        let creator = token_info.creator;
+        creator.require_auth();
+
+        if !token_info.is_launched {
+            return Err(ContractError::NotLaunched);
+        }
+
+        if total_amount <= 0 || expiry_ledger <= env.ledger().sequence() {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let balance = storage::get_balance(&env, &token_id, &creator);
+        if total_amount > balance {
+            return Err(ContractError::InsufficientFunds);
+        }
+        storage::set_balance(&env, &token_id, &creator, balance - total_amount);
+
+        let airdrop_id = storage::next_airdrop_id(&env);
+        storage::set_airdrop(&env, airdrop_id, &Airdrop {
+            token_id: token_id.clone(),
+            creator,
+            merkle_root,
+            total_amount,
+            claimed_amount: 0,
+            expiry_ledger,
+            reclaimed: false,
+        });
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("airdrop"),),
+            (token_id, airdrop_id, total_amount, expiry_ledger)
+        );
+
+        Ok(airdrop_id)
+    }
+
+    /// Claim a recipient's airdrop entry with a Merkle proof before expiry
+    pub fn claim_airdrop(
+        env: Env,
+        airdrop_id: u32,
+        index: u32,
+        recipient: Address,
+        amount: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<(), ContractError> {
+        let mut airdrop = storage::get_airdrop(&env, airdrop_id)
+            .ok_or(ContractError::NothingToClaim)?;
+
+        if env.ledger().sequence() >= airdrop.expiry_ledger {
+            return Err(ContractError::ClaimExpired);
+        }
+
+        let (word, _) = AirdropBitmap::position(index);
+        let bits = AirdropBitmap::set_claimed(storage::get_airdrop_bitmap(&env, airdrop_id, word), index)?;
+
+        let leaf = MerkleProof::indexed_leaf_hash(&env, index, &recipient, amount);
+        if !MerkleProof::verify(&env, &airdrop.merkle_root, &leaf, &proof) {
+            return Err(ContractError::InvalidProof);
+        }
+
+        if airdrop.claimed_amount + amount > airdrop.total_amount {
+            return Err(ContractError::AllocationExceeded);
+        }
+
+        let token_info = storage::get_token(&env, &airdrop.token_id)
+            .ok_or(ContractError::TokenNotFound)?;
+        if Self::holding_room(&env, &airdrop.token_id, &token_info, &recipient).is_some_and(|room| amount > room) {
+            return Err(ContractError::WalletHoldingCapExceeded);
+        }
+
+        storage::set_airdrop_bitmap(&env, airdrop_id, word, bits);
+        airdrop.claimed_amount += amount;
+        storage::set_airdrop(&env, airdrop_id, &airdrop);
+
+        let balance = storage::get_balance(&env, &airdrop.token_id, &recipient);
+        storage::set_balance(&env, &airdrop.token_id, &recipient, balance + amount);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("dropclaim"),),
+            (airdrop.token_id, airdrop_id, index, recipient, amount)
+        );
+
+        Ok(())
+    }
+
+    /// Return an expired airdrop's unclaimed tokens to the creator
+    pub fn reclaim_airdrop(env: Env, airdrop_id: u32) -> Result<i128, ContractError> {
+        let mut airdrop = storage::get_airdrop(&env, airdrop_id)
+            .ok_or(ContractError::NothingToClaim)?;
+
+        airdrop.creator.require_auth();
+
+        if env.ledger().sequence() < airdrop.expiry_ledger {
+            return Err(ContractError::ClaimNotExpired);
+        }
+
+        let unclaimed = airdrop.total_amount - airdrop.claimed_amount;
+        if airdrop.reclaimed || unclaimed <= 0 {
+            return Err(ContractError::NothingToClaim);
+        }
+
+        airdrop.reclaimed = true;
+        storage::set_airdrop(&env, airdrop_id, &airdrop);
+
+        let balance = storage::get_balance(&env, &airdrop.token_id, &airdrop.creator);
+        storage::set_balance(&env, &airdrop.token_id, &airdrop.creator, balance + unclaimed);
+
+        Ok(unclaimed)
+    }
+
+    /// Get an airdrop by id
+    pub fn get_airdrop(env: Env, airdrop_id: u32) -> Option<Airdrop> {
+        storage::get_airdrop(&env, airdrop_id)
+    }
+
+    /// Check whether an airdrop entry has been claimed
+    pub fn is_airdrop_claimed(env: Env, airdrop_id: u32, index: u32) -> bool {
+        let (word, _) = AirdropBitmap::position(index);
+        AirdropBitmap::is_claimed(storage::get_airdrop_bitmap(&env, airdrop_id, word), index)
+    }
+
+    /// Get a holder's balance of a token
+    pub fn get_balance(env: Env, token_id: String, holder: Address) -> i128 {
+        storage::get_balance(&env, &token_id, &holder)
+    }
+
+    /// Get total number of tokens created
+    pub fn get_token_count(env: Env) -> u32 {
+        storage::get_token_count(&env)
+    }
+
+    /// Get the id of the token at `index` in creation order
+    pub fn get_token_id_at(env: Env, index: u32) -> Option<String> {
+        storage::get_token_at(&env, index)
+    }
+}
+
+impl LaunchpadContract {
+    fn execute_purchase(
+        env: &Env,
+        buyer: &Address,
+        token_id: String,
+        xlm_amount: i128,
+    ) -> Result<PurchaseResult, ContractError> {
+        if xlm_amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let token_info = Self::get_open_token(env, &token_id)?;
+
+        // The platform fee comes out of the amount sent in
+        let fee = PlatformFee::calculate_fee(xlm_amount, Self::fee_bps(env))?;
+        let xlm_cost = xlm_amount - fee;
+
+        let (tokens_to_receive, xlm_cost) = Self::price_purchase(&token_info, PurchaseSize::Quote(xlm_cost))?;
+        Self::apply_purchase(env, buyer, token_id, token_info, tokens_to_receive, xlm_cost, fee)
+    }
+
+    /// Price a buy by the integral of the curve, the same way sales are priced
+    ///
+    /// Returns the tokens bought and the quote asset charged to the reserve. A
+    /// quote budget is charged in full, so its rounding stays in the reserve.
+    fn price_purchase(token_info: &TokenInfo, size: PurchaseSize) -> Result<(i128, i128), ContractError> {
+        match size {
+            PurchaseSize::Tokens(token_amount) => {
+                if token_amount > Self::curve_supply(token_info) - token_info.tokens_sold {
+                    return Err(ContractError::InsufficientSupply);
+                }
+
+                let cost = BondingCurve::calculate_cost_between(
+                    &token_info.curve_params,
+                    token_info.tokens_sold,
+                    token_info.tokens_sold + token_amount,
+                    token_info.total_supply,
+                )?;
+                Ok((token_amount, cost))
+            }
+            PurchaseSize::Quote(budget) => {
+                let tokens = BondingCurve::calculate_tokens_for_cost(
+                    &token_info.curve_params,
+                    budget,
+                    token_info.tokens_sold,
+                    token_info.total_supply,
+                )?;
+
+                if tokens <= 0 {
+                    return Err(ContractError::InvalidAmount);
+                }
+
+                Ok((tokens, budget))
+            }
+        }
+    }
+
+    fn execute_sale(
+        env: &Env,
+        seller: &Address,
+        token_id: &String,
+        token_amount: i128,
+    ) -> Result<(Address, SellResult), ContractError> {
+        if token_amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut token_info = Self::get_open_token(env, token_id)?;
+
+        let balance = storage::get_balance(env, token_id, seller);
+        if token_amount > balance {
+            return Err(ContractError::InsufficientFunds);
+        }
+
+        // Refund the integral of the curve over the range sold, never more than the reserve
+        let tokens_sold_after = token_info.tokens_sold - token_amount;
+        let proceeds = BondingCurve::calculate_cost_between(
+            &token_info.curve_params,
+            tokens_sold_after,
+            token_info.tokens_sold,
+            token_info.total_supply,
+        )?.min(token_info.quote_raised);
+        let fee = PlatformFee::calculate_fee(proceeds, Self::fee_bps(env))?;
+
+        // Recent buyers pay a surcharge that decays with their average holding time
+        let sell_fee = storage::get_sell_fee(env, token_id);
+        let surcharge = match &sell_fee {
+            Some(schedule) => {
+                let since = storage::get_holding_ledger(env, token_id, seller).unwrap_or(token_info.creation_ledger);
+                SellFee::calculate_surcharge(schedule, proceeds, env.ledger().sequence().saturating_sub(since))?
+            }
+            None => 0,
+        };
+        let surcharge = surcharge.min(proceeds - fee);
+        let payout = proceeds - fee - surcharge;
+
+        let price_after = BondingCurve::get_current_price(
+            &token_info.curve_params,
+            tokens_sold_after,
+            token_info.total_supply,
+        )?;
+        Self::check_circuit_breaker(env, token_id, token_info.current_price, price_after)?;
+
+        // A surcharge kept in the reserve backs the remaining holders
+        let to_reserve = sell_fee.map(|schedule| schedule.to_reserve).unwrap_or(false);
+        token_info.tokens_sold = tokens_sold_after;
+        token_info.quote_raised -= if to_reserve { proceeds - surcharge } else { proceeds };
+        token_info.current_price = price_after;
+        Self::record_price(env, token_id, price_after)?;
+        storage::set_token(env, token_id, &token_info);
+        storage::set_balance(env, token_id, seller, balance - token_amount);
+
+        Self::pay_platform_fee(env, token_id, &token_info.quote_asset, seller, fee)?;
+        if surcharge > 0 {
+            if !to_reserve {
+                let fee_config = storage::get_fee_config(env).ok_or(ContractError::NotInitialized)?;
+                AssetManager::transfer_quote(
+                    env,
+                    &token_info.quote_asset,
+                    &env.current_contract_address(),
+                    &fee_config.fee_recipient,
+                    surcharge,
+                )?;
+            }
+
+            env.events().publish(
+                (soroban_sdk::symbol_short!("surcharge"),),
+                (token_id.clone(), seller.clone(), surcharge, to_reserve)
+            );
+        }
+        LaunchHooks::queue_trade(env, token_id, seller, false, token_amount, payout, price_after);
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("sale"),),
+            (token_id.clone(), seller.clone(), token_amount, payout)
+        );
+
+        Ok((token_info.quote_asset, SellResult {
+            quote_received: payout,
+            new_price: price_after,
+        }))
+    }
+
+    fn apply_purchase(
+        env: &Env,
+        buyer: &Address,
+        token_id: String,
+        mut token_info: TokenInfo,
+        tokens_to_receive: i128,
+        xlm_cost: i128,
+        fee: i128,
+    ) -> Result<PurchaseResult, ContractError> {
+        // Enforce anti-sniping limits set at creation; tokens escrowed in sell orders still count as held
+        let purchased = storage::get_purchased(env, &token_id, buyer);
+        let holding = storage::get_balance(env, &token_id, buyer);
+        LaunchGuard::check_purchase(
+            &token_info.protection,
+            *buyer == token_info.creator,
+            env.ledger().sequence().saturating_sub(token_info.creation_ledger),
+            purchased,
+            holding + storage::get_order_escrow(env, &token_id, buyer),
+            tokens_to_receive,
+            token_info.total_supply,
+        )?;
+
+        // Reject trades that move the price too far
+        let price_before = BondingCurve::get_current_price(
+            &token_info.curve_params,
+            token_info.tokens_sold,
+            token_info.total_supply,
+        )?;
+        let price_after = BondingCurve::get_current_price(
+            &token_info.curve_params,
+            token_info.tokens_sold + tokens_to_receive,
+            token_info.total_supply,
+        )?;
+        Self::check_circuit_breaker(env, &token_id, price_before, price_after)?;
+
+        // The creator's reserve is never sold on the curve
+        if tokens_to_receive > Self::curve_supply(&token_info) - token_info.tokens_sold {
+            return Err(ContractError::InsufficientSupply);
+        }
+
+        // Update token info
+        token_info.tokens_sold += tokens_to_receive;
+        token_info.quote_raised += xlm_cost;
+        token_info.current_price = price_after;
+        Self::record_price(env, &token_id, price_after)?;
+
+        // Check launch conditions
+        let launch_triggered = Self::update_launch_status(env, &mut token_info);
+
+        // Save updated info
+        storage::set_token(env, &token_id, &token_info);
+        storage::set_purchased(env, &token_id, buyer, purchased + tokens_to_receive);
+        storage::set_balance(env, &token_id, buyer, holding + tokens_to_receive);
+        if storage::get_sell_fee(env, &token_id).is_some() {
+            let since = storage::get_holding_ledger(env, &token_id, buyer).unwrap_or(token_info.creation_ledger);
+            let ledger = SellFee::average_ledger(holding, since, tokens_to_receive, env.ledger().sequence())?;
+            storage::set_holding_ledger(env, &token_id, buyer, ledger);
+        }
+
+        Self::pay_platform_fee(env, &token_id, &token_info.quote_asset, buyer, fee)?;
+        LaunchHooks::queue_trade(env, &token_id, buyer, true, tokens_to_receive, xlm_cost + fee, price_after);
+
+        // Emit purchase event
+        env.events().publish(
+            (soroban_sdk::symbol_short!("purchase"),),
+            (token_id, buyer.clone(), xlm_cost + fee, tokens_to_receive)
+        );
+
+        Ok(PurchaseResult {
+            tokens_received: tokens_to_receive,
+            quote_spent: xlm_cost + fee,
+            new_price: token_info.current_price,
+            launch_triggered,
+        })
+    }
+
+    /// Tokens a holder may still be credited under the holding cap, which lifts at graduation
+    ///
+    /// Tokens escrowed in the holder's sell orders count as held.
+    fn holding_room(env: &Env, token_id: &String, token_info: &TokenInfo, holder: &Address) -> Option<i128> {
+        if token_info.is_launched {
+            return None;
+        }
+
+        let holding = storage::get_balance(env, token_id, holder) + storage::get_order_escrow(env, token_id, holder);
+        LaunchGuard::holding_room(&token_info.protection, holding, token_info.total_supply)
+    }
+
+    fn get_open_token(env: &Env, token_id: &String) -> Result<TokenInfo, ContractError> {
+        Self::require_not_paused(env)?;
+
+        let token_info = storage::get_token(env, token_id)
+            .ok_or(ContractError::TokenNotFound)?;
+
+        if token_info.is_launched {
+            return Err(ContractError::AlreadyLaunched);
+        }
+
+        if token_info.refunds_active {
+            return Err(ContractError::InvalidTokenState);
+        }
+
+        Ok(token_info)
+    }
+
+    fn pay_keeper_bounty(
+        env: &Env,
+        token_id: &String,
+        token_info: &mut TokenInfo,
+        keeper: &Address,
+    ) -> Result<i128, ContractError> {
+        let bounty = KeeperBounty::calculate_bounty(token_info.quote_raised, storage::get_keeper_bounty_bps(env))?;
+        if bounty <= 0 {
+            return Ok(0);
+        }
+
+        token_info.quote_raised -= bounty;
+        AssetManager::transfer_quote(
+            env,
+            &token_info.quote_asset,
+            &env.current_contract_address(),
+            keeper,
+            bounty,
+        )?;
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("bounty"),),
+            (token_id.clone(), keeper.clone(), bounty)
+        );
+
+        Ok(bounty)
+    }
+
+    fn quote_asset(env: &Env, token_id: &String) -> Result<Address, ContractError> {
+        let token_info = storage::get_token(env, token_id)
+            .ok_or(ContractError::TokenNotFound)?;
+
+        Ok(token_info.quote_asset)
+    }
+
+    fn update_launch_status(env: &Env, token_info: &mut TokenInfo) -> bool {
+        if token_info.is_launched || !Self::launch_reached(token_info) {
+            return false;
+        }
+
+        // Creator vesting is measured from graduation
+        token_info.is_launched = true;
+        token_info.launch_ledger = env.ledger().sequence();
+        true
+    }
+
+    fn curve_supply(token_info: &TokenInfo) -> i128 {
+        token_info.total_supply - token_info.creator_reserved
+    }
+
+    fn creator_vested(env: &Env, token_info: &TokenInfo) -> Result<i128, ContractError> {
+        if !token_info.is_launched {
+            return Ok(0);
+        }
+
+        Vesting::vested_amount(
+            &token_info.creator_allocation,
+            token_info.creator_reserved,
+            env.ledger().sequence().saturating_sub(token_info.launch_ledger),
+        )
+    }
+
+    fn launch_reached(token_info: &TokenInfo) -> bool {
+        let raised_reached = token_info.launch_threshold > 0
+            && token_info.quote_raised >= token_info.launch_threshold;
+        // The creator's reserve never reaches the curve, so the percentage is of the curve's supply
+        let sold_reached = token_info.launch_threshold_percent > 0
+            && token_info.tokens_sold * 100
+                >= Self::curve_supply(token_info) * token_info.launch_threshold_percent as i128;
+
+        raised_reached || sold_reached
+    }
+
+    fn fee_bps(env: &Env) -> u32 {
+        storage::get_fee_config(env).map(|config| config.fee_bps).unwrap_or(0)
+    }
+
+    fn pay_platform_fee(
+        env: &Env,
+        token_id: &String,
+        quote_asset: &Address,
+        trader: &Address,
+        fee: i128,
+    ) -> Result<(), ContractError> {
+        if fee <= 0 {
+            return Ok(());
+        }
+
+        let fee_config = storage::get_fee_config(env).ok_or(ContractError::NotInitialized)?;
+
+        // Referred traders route part of the fee to their referrer's claimable balance
+        let mut platform_fee = fee;
+        if let Some(referrer) = storage::get_referrer(env, trader) {
+            let (remaining, reward) = ReferralProgram::split_fee(fee, storage::get_referral_share_bps(env))?;
+            if reward > 0 {
+                let rewards = storage::get_referral_rewards(env, &referrer, quote_asset);
+                storage::set_referral_rewards(env, &referrer, quote_asset, rewards + reward);
+
+                env.events().publish(
+                    (soroban_sdk::symbol_short!("referral"),),
+                    (token_id.clone(), trader.clone(), referrer, reward)
+                );
+            }
+            platform_fee = remaining;
+        }
+
+        AssetManager::transfer_quote(
+            env,
+            quote_asset,
+            &env.current_contract_address(),
+            &fee_config.fee_recipient,
+            platform_fee,
+        )?;
+
+        env.events().publish(
+            (soroban_sdk::symbol_short!("fee"),),
+            (token_id.clone(), fee, trader.clone())
+        );
+
+        Ok(())
+    }
+
+    fn register_referral(
+        env: &Env,
+        trader: &Address,
+        referrer: &Option<Address>,
+    ) -> Result<(), ContractError> {
+        let Some(referrer) = referrer else {
+            return Ok(());
+        };
+
+        let current_referrer = storage::get_referrer(env, trader);
+        ReferralProgram::check_referral(
+            trader,
+            referrer,
+            current_referrer.clone(),
+            storage::get_referrer(env, referrer).is_some(),
+            storage::has_referrals(env, trader),
+        )?;
+
+        if current_referrer.is_none() {
+            storage::set_referrer(env, trader, referrer);
+            storage::set_has_referrals(env, referrer);
+        }
+
+        Ok(())
+    }
+
+    fn require_public_trading(env: &Env, token_id: &String) -> Result<(), ContractError> {
+        // Only allowlisted buyers may trade until the presale ends
+        if let Some(presale) = storage::get_presale(env, token_id) {
+            if env.ledger().sequence() < presale.end_ledger {
+                return Err(ContractError::PresaleActive);
+            }
+        }
+
+        // The curve opens once the auction has been settled
+        if let Some(auction) = storage::get_auction(env, token_id) {
+            if !auction.settled {
+                return Err(ContractError::AuctionActive);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn require_not_shutdown(env: &Env) -> Result<(), ContractError> {
+        if storage::is_shutdown(env) {
+            return Err(ContractError::PlatformShutdown);
+        }
+
+        Ok(())
+    }
+
+    fn require_not_paused(env: &Env) -> Result<(), ContractError> {
+        Self::require_not_shutdown(env)?;
+
+        // Records under the previous schema are invisible until `migrate` completes
+        if storage::is_paused(env) || StorageMigration::is_pending(env) {
+            return Err(ContractError::Paused);
+        }
+
+        Ok(())
+    }
+
+    fn require_admin(env: &Env) -> Result<Address, ContractError> {
+        // Once an admin set takes over, the single admin key has no direct powers
+        if storage::get_governance(env).is_some() {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let admin = storage::get_admin(env).ok_or(ContractError::NotInitialized)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    fn require_governance_admin(env: &Env, admin: &Address) -> Result<Governance, ContractError> {
+        admin.require_auth();
+
+        let governance = storage::get_governance(env).ok_or(ContractError::NotInitialized)?;
+        if !AdminGovernance::is_admin(&governance, admin) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        Ok(governance)
+    }
+
+    fn validate_action(env: &Env, action: &AdminAction) -> Result<(), ContractError> {
+        match action {
+            AdminAction::SetFeeConfig(fee_config) => PlatformFee::validate_fee_bps(fee_config.fee_bps),
+            AdminAction::SetReferralShare(share_bps) => ReferralProgram::validate_share_bps(*share_bps),
+            AdminAction::SetKeeperBounty(bounty_bps) => KeeperBounty::validate_bounty_bps(*bounty_bps),
+            AdminAction::SetTradeLimits(limits) => CircuitBreaker::validate_limits(limits),
+            AdminAction::SetTokenTradeLimits(token_id, limits) => {
+                if !storage::has_token(env, token_id) {
+                    return Err(ContractError::TokenNotFound);
+                }
+
+                CircuitBreaker::validate_limits(limits)
+            }
+            AdminAction::ClearTokenTradeLimits(token_id) | AdminAction::ClearTokenHook(token_id) => {
+                if !storage::has_token(env, token_id) {
+                    return Err(ContractError::TokenNotFound);
+                }
+
+                Ok(())
+            }
+            AdminAction::SetCurvePreset(preset_id, preset) => {
+                CurvePresets::validate_preset(&storage::get_curve_presets(env), *preset_id, preset)
+            }
+            AdminAction::RemoveCurvePreset(preset_id) => {
+                if !storage::get_curve_presets(env).contains_key(*preset_id) {
+                    return Err(ContractError::InvalidCurveParameters);
+                }
+
+                Ok(())
+            }
+            AdminAction::SetGovernance(governance) => AdminGovernance::validate_governance(governance),
+            AdminAction::Shutdown => Self::require_not_shutdown(env),
+            AdminAction::Unpause | AdminAction::Upgrade(_) | AdminAction::LockTokenCreation => Ok(()),
+        }
+    }
+
+    fn apply_action(env: &Env, action: AdminAction) -> Result<(), ContractError> {
+        Self::validate_action(env, &action)?;
+
+        match action {
+            AdminAction::SetFeeConfig(fee_config) => storage::set_fee_config(env, &fee_config),
+            AdminAction::SetReferralShare(share_bps) => storage::set_referral_share_bps(env, share_bps),
+            AdminAction::SetKeeperBounty(bounty_bps) => storage::set_keeper_bounty_bps(env, bounty_bps),
+            AdminAction::SetTradeLimits(limits) => storage::set_trade_limits(env, &limits),
+            AdminAction::SetTokenTradeLimits(token_id, limits) => {
+                storage::set_token_trade_limits(env, &token_id, &Some(limits))
+            }
+            AdminAction::ClearTokenTradeLimits(token_id) => {
+                storage::set_token_trade_limits(env, &token_id, &None)
+            }
+            AdminAction::ClearTokenHook(token_id) => storage::set_launch_hook(env, &token_id, &None),
+            AdminAction::SetCurvePreset(preset_id, preset) => {
+                let mut presets = storage::get_curve_presets(env);
+                presets.set(preset_id, preset);
+                storage::set_curve_presets(env, &presets);
+            }
+            AdminAction::RemoveCurvePreset(preset_id) => {
+                let mut presets = storage::get_curve_presets(env);
+                presets.remove(preset_id);
+                storage::set_curve_presets(env, &presets);
+            }
+            AdminAction::SetGovernance(governance) => storage::set_governance(env, &governance),
+            AdminAction::Unpause => storage::set_paused(env, false),
+            AdminAction::Shutdown => {
+                storage::set_shutdown(env);
+
+                env.events().publish(
+                    (soroban_sdk::symbol_short!("shutdown"),),
+                    env.ledger().sequence()
+                );
+            }
+            AdminAction::Upgrade(new_wasm_hash) => {
+                env.deployer().update_current_contract_wasm(new_wasm_hash)
+            }
+            AdminAction::LockTokenCreation => storage::set_creation_locked(env),
+        }
+
+        Ok(())
+    }
+
+    fn record_price(env: &Env, token_id: &String, price: i128) -> Result<(), ContractError> {
+        let mut oracle = storage::get_oracle(env, token_id).ok_or(ContractError::TokenNotFound)?;
+        PriceOracle::record(&mut oracle, env.ledger().timestamp(), price)?;
+        storage::set_oracle(env, token_id, &oracle);
+
+        Ok(())
+    }
+
+    fn check_circuit_breaker(
+        env: &Env,
+        token_id: &String,
+        price_before: i128,
+        price_after: i128,
+    ) -> Result<(), ContractError> {
+        let limits = storage::get_token_trade_limits(env, token_id)
+            .unwrap_or_else(|| storage::get_trade_limits(env));
+
+        // The first trade of a ledger sets the price the ledger move is measured from
+        let ledger = env.ledger().sequence();
+        let open_price = match storage::get_ledger_open_price(env, token_id) {
+            Some((open_ledger, open_price)) if open_ledger == ledger => open_price,
+            _ => {
+                storage::set_ledger_open_price(env, token_id, ledger, price_before);
+                price_before
+            }
+        };
+
+        CircuitBreaker::check_trade(&limits, price_before, price_after, open_price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use soroban_sdk::{testutils::{Address as _, Ledger}, token, vec, Env};
+    use crate::types::{AuctionConfig, CurveType, CurveParameters};
+
+    fn setup_launchpad<'a>(env: &Env) -> (LaunchpadContractClient<'a>, Address) {
+        env.mock_all_auths();
+        let contract_id = env.register(LaunchpadContract, ());
+        let client = LaunchpadContractClient::new(env, &contract_id);
+
+        let xlm_token = env.register_stellar_asset_contract_v2(Address::generate(env)).address();
+        client.initialize(&Address::generate(env), &xlm_token);
+
+        (client, xlm_token)
+    }
+
+    fn funded_account(env: &Env, xlm_token: &Address) -> Address {
+        let account = Address::generate(env);
+        token::StellarAssetClient::new(env, xlm_token).mint(&account, &1_000_000_000_000);
+        account
+    }
+
+    fn linear_curve() -> CurveParameters {
+        CurveParameters {
+            curve_type: CurveType::Linear,
+            base_price: 1000,
+            price_multiplier: 9000,
+        }
+    }
+
+    fn create_protected_token<'a>(
+        env: &Env,
+        protection: LaunchProtection,
+    ) -> (LaunchpadContractClient<'a>, Address, String, Address) {
+        let (client, xlm_token) = setup_launchpad(env);
+
+        let creator = funded_account(env, &xlm_token);
+        let token_id = client.create_token(
+            &creator,
+            &String::from_str(env, "Protected Token"),
+            &String::from_str(env, "SAFE"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &Some(LaunchOptions {
+                protection,
+                mode: LaunchMode::Open,
+                creator_allocation: CreatorAllocation::none(),
+                launch_deadline_ledgers: 0,
+                hook: None,
+                hook_trades: false,
+                sell_fee: SellFeeSchedule::none(),
+            }),
+        );
+
+        (client, creator, token_id, xlm_token)
+    }
+
+    #[test]
+    fn test_create_token() {
+        let env = Env::default();
+        let contract_id = env.register(LaunchpadContract, ());
+        let client = LaunchpadContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let name = String::from_str(&env, "Test Token");
+        let symbol = String::from_str(&env, "TEST");
+        let total_supply = 1_000_000i128;
+        let launch_threshold = 100_000_000i128; // 10 XLM
+        let launch_threshold_percent = 80u32;
+        let curve_params = CurveParameters {
+            curve_type: CurveType::Linear,
+            base_price: 1000,
+            price_multiplier: 9000,
+        };
+
+        let token_id = client.create_token(
+            &creator,
+            &name,
+            &symbol,
+            &total_supply,
+            &launch_threshold,
+            &launch_threshold_percent,
+            &CurveSource::Custom(curve_params),
+            &Some(Address::generate(&env)),
+            &None,
         );
 
         // Verify token was created
         let token_info = client.get_token_info(&token_id);
-        assert_eq!(token_info.name, name);
-        assert_eq!(token_info.symbol, symbol);
-        assert_eq!(token_info.total_supply, total_supply);
-        assert_eq!(token_info.tokens_sold, 0);
-        assert_eq!(token_info.is_launched, false);
+        assert_eq!(token_info.name, name);
+        assert_eq!(token_info.symbol, symbol);
+        assert_eq!(token_info.total_supply, total_supply);
+        assert_eq!(token_info.tokens_sold, 0);
+        assert_eq!(token_info.is_launched, false);
+    }
+
+    #[test]
+    fn test_wallet_buy_cap_during_window() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection {
+            window_ledgers: 10,
+            max_buy_per_wallet: Some(100),
+            max_wallet_percent: None,
+            max_dev_buy: None,
+        });
+        let sniper = funded_account(&env, &xlm_token);
+
+        // 100 tokens cost 100_045 stroops along the curve, exactly the cap
+        client.buy_tokens(&sniper, &token_id, &100_045, &None);
+        assert_eq!(client.get_balance(&token_id, &sniper), 100);
+
+        let result = client.try_buy_tokens(&sniper, &token_id, &10_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::WalletBuyCapExceeded)));
+
+        // The cap lifts once the window has passed
+        env.ledger().with_mut(|li| li.sequence_number += 10);
+        client.buy_tokens(&sniper, &token_id, &10_000, &None);
+    }
+
+    #[test]
+    fn test_wallet_holding_cap() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection {
+            window_ledgers: 0,
+            max_buy_per_wallet: None,
+            max_wallet_percent: Some(1),
+            max_dev_buy: None,
+        });
+        let whale = funded_account(&env, &xlm_token);
+
+        // 1% of 1_000_000 supply = 10_000 tokens
+        let result = client.try_buy_tokens(&whale, &token_id, &20_000_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::WalletHoldingCapExceeded)));
+
+        // Every buy path checks the wallet that ends up holding the tokens
+        let result = client.try_buy_exact_tokens(&whale, &token_id, &10_001, &i128::MAX, &None);
+        assert_eq!(result, Err(Ok(ContractError::WalletHoldingCapExceeded)));
+        let sponsor = funded_account(&env, &xlm_token);
+        let result = client.try_buy_tokens_for(&sponsor, &whale, &token_id, &20_000_000, &0);
+        assert_eq!(result, Err(Ok(ContractError::WalletHoldingCapExceeded)));
+
+        let info = client.get_token_info(&token_id);
+        assert_eq!(info.protection.max_wallet_percent, Some(1));
+
+        // Tokens escrowed in a sell order still count toward the cap
+        let held = client.buy_tokens(&whale, &token_id, &8_000_000, &None).tokens_received;
+        let order_id = client.place_order(&token_id, &LimitOrder {
+            owner: whale.clone(),
+            side: OrderSide::Sell,
+            amount: held,
+            trigger_price: info.current_price * 2,
+            min_out: 0,
+            executor_fee: 0,
+            expiry_ledger: env.ledger().sequence() + 100,
+        });
+        assert_eq!(client.get_balance(&token_id, &whale), 0);
+        let result = client.try_buy_tokens(&whale, &token_id, &8_000_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::WalletHoldingCapExceeded)));
+
+        client.cancel_order(&token_id, &order_id);
+        assert_eq!(client.get_balance(&token_id, &whale), held);
+    }
+
+    #[test]
+    fn test_dev_buy_limit() {
+        let env = Env::default();
+        let (client, creator, token_id, _) = create_protected_token(&env, LaunchProtection {
+            window_ledgers: 0,
+            max_buy_per_wallet: None,
+            max_wallet_percent: None,
+            max_dev_buy: Some(50),
+        });
+
+        let result = client.try_buy_tokens(&creator, &token_id, &100_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::DevBuyLimitExceeded)));
+
+        client.buy_tokens(&creator, &token_id, &50_011, &None);
+        assert_eq!(client.get_balance(&token_id, &creator), 50);
+    }
+
+    #[test]
+    fn test_price_impact_circuit_breaker() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let trader = funded_account(&env, &xlm_token);
+
+        // 5% max impact per trade, 8% max move per ledger
+        client.set_trade_limits(&TradeLimits {
+            max_trade_impact_bps: Some(500),
+            max_ledger_move_bps: Some(800),
+        });
+
+        // 200 XLM would buy out the whole curve
+        let result = client.try_buy_tokens(&trader, &token_id, &2_000_000_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::PriceImpactExceeded)));
+
+        // Two ~4.5% moves in one ledger (1000 -> 1045 -> 1087) trip the ledger limit
+        client.buy_tokens(&trader, &token_id, &5_000_000, &None);
+        let result = client.try_buy_tokens(&trader, &token_id, &5_000_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::LedgerMoveExceeded)));
+
+        // The next ledger starts a fresh window
+        env.ledger().with_mut(|li| li.sequence_number += 1);
+        client.buy_tokens(&trader, &token_id, &5_000_000, &None);
+    }
+
+    #[test]
+    fn test_token_trade_limit_override() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+
+        let token_limits = TradeLimits {
+            max_trade_impact_bps: Some(100),
+            max_ledger_move_bps: None,
+        };
+        client.set_token_trade_limits(&token_id, &Some(token_limits.clone()));
+        assert_eq!(client.get_trade_limits(&token_id), token_limits);
+
+        let result = client.try_buy_tokens(&funded_account(&env, &xlm_token), &token_id, &50_000_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::PriceImpactExceeded)));
+
+        // Clearing the override falls back to the platform limits
+        client.set_token_trade_limits(&token_id, &None);
+        assert_eq!(client.get_trade_limits(&token_id), TradeLimits::none());
+    }
+
+    #[test]
+    fn test_presale_allowlist() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+
+        let alice = funded_account(&env, &xlm_token);
+        let bob = funded_account(&env, &xlm_token);
+        let outsider = funded_account(&env, &xlm_token);
+
+        // Allowlist: alice may spend 5 XLM, bob 1 XLM
+        let leaf_alice = MerkleProof::leaf_hash(&env, &alice, 50_000_000);
+        let leaf_bob = MerkleProof::leaf_hash(&env, &bob, 10_000_000);
+        let root = MerkleProof::hash_pair(&env, &leaf_alice, &leaf_bob);
+
+        let end_ledger = env.ledger().sequence() + 100;
+        let token_id = client.create_token(
+            &Address::generate(&env),
+            &String::from_str(&env, "Presale Token"),
+            &String::from_str(&env, "PRE"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &Some(LaunchOptions {
+                protection: LaunchProtection::none(),
+                mode: LaunchMode::Presale(PresaleConfig { merkle_root: root, end_ledger }),
+                creator_allocation: CreatorAllocation::none(),
+                launch_deadline_ledgers: 0,
+                hook: None,
+                hook_trades: false,
+                sell_fee: SellFeeSchedule::none(),
+            }),
+        );
+
+        // Public buys are closed during the presale
+        let result = client.try_buy_tokens(&outsider, &token_id, &1_000_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::PresaleActive)));
+
+        // Allowlisted buyers prove membership and stay within their allocation
+        let proof_alice = vec![&env, leaf_bob.clone()];
+        client.buy_presale(&alice, &token_id, &30_000_000, &50_000_000, &proof_alice, &None);
+        let result = client.try_buy_presale(&alice, &token_id, &30_000_000, &50_000_000, &proof_alice, &None);
+        assert_eq!(result, Err(Ok(ContractError::AllocationExceeded)));
+
+        // Claiming a larger allocation or someone else's proof fails
+        let result = client.try_buy_presale(&bob, &token_id, &1_000_000, &50_000_000, &vec![&env, leaf_alice.clone()], &None);
+        assert_eq!(result, Err(Ok(ContractError::InvalidProof)));
+        let result = client.try_buy_presale(&outsider, &token_id, &1_000_000, &50_000_000, &proof_alice, &None);
+        assert_eq!(result, Err(Ok(ContractError::InvalidProof)));
+
+        // Trading opens to everyone after the deadline
+        env.ledger().with_mut(|li| li.sequence_number = end_ledger);
+        let result = client.try_buy_presale(&alice, &token_id, &1_000_000, &50_000_000, &proof_alice, &None);
+        assert_eq!(result, Err(Ok(ContractError::PresaleEnded)));
+        client.buy_tokens(&outsider, &token_id, &1_000_000, &None);
+    }
+
+    #[test]
+    fn test_opening_batch_auction() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let xlm = token::Client::new(&env, &xlm_token);
+
+        let token_id = client.create_token(
+            &Address::generate(&env),
+            &String::from_str(&env, "Fair Token"),
+            &String::from_str(&env, "FAIR"),
+            &1_000_000i128,
+            &10_000_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &Some(LaunchOptions {
+                protection: LaunchProtection::none(),
+                mode: LaunchMode::Auction(AuctionConfig { duration_ledgers: 10, max_tokens: 20_000 }),
+                creator_allocation: CreatorAllocation::none(),
+                launch_deadline_ledgers: 0,
+                hook: None,
+                hook_trades: false,
+                sell_fee: SellFeeSchedule::none(),
+            }),
+        );
+
+        // Curve buying and limit orders wait for the auction
+        let alice = funded_account(&env, &xlm_token);
+        let bob = funded_account(&env, &xlm_token);
+        let result = client.try_buy_tokens(&alice, &token_id, &1_000_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::AuctionActive)));
+        let result = client.try_place_order(&token_id, &LimitOrder {
+            owner: alice.clone(),
+            side: OrderSide::Buy,
+            amount: 1_000_000,
+            trigger_price: 1_000_000,
+            min_out: 0,
+            executor_fee: 0,
+            expiry_ledger: env.ledger().sequence() + 100,
+        });
+        assert_eq!(result, Err(Ok(ContractError::AuctionActive)));
+
+        // 4 XLM of commitments against the 2.18 XLM the first 20_000 tokens cost
+        client.commit_auction(&alice, &token_id, &10_000_000);
+        client.commit_auction(&bob, &token_id, &30_000_000);
+        assert_eq!(client.try_settle_auction(&token_id), Err(Ok(ContractError::AuctionNotEnded)));
+
+        env.ledger().with_mut(|li| li.sequence_number += 10);
+        let result = client.try_commit_auction(&alice, &token_id, &1_000_000);
+        assert_eq!(result, Err(Ok(ContractError::AuctionEnded)));
+
+        let auction = client.settle_auction(&token_id);
+        assert_eq!(auction.tokens_cleared, 20_000);
+        assert_eq!(auction.quote_cleared, 21_800_000);
+        assert_eq!(auction.clearing_price, 1_090);
+
+        // Both participants pay the same price per token and get the excess back
+        let alice_before = xlm.balance(&alice);
+        let claim = client.claim_auction(&alice, &token_id);
+        assert_eq!(claim, AuctionClaim { tokens_received: 5_000, quote_refunded: 4_550_000 });
+        assert_eq!(xlm.balance(&alice), alice_before + 4_550_000);
+
+        let claim = client.claim_auction(&bob, &token_id);
+        assert_eq!(claim, AuctionClaim { tokens_received: 15_000, quote_refunded: 13_650_000 });
+        assert_eq!(client.get_balance(&token_id, &bob), 15_000);
+        assert_eq!(client.try_claim_auction(&bob, &token_id), Err(Ok(ContractError::NothingToClaim)));
+        assert_eq!(xlm.balance(&client.address), 21_800_000);
+
+        // Curve trading continues from the cleared supply
+        let info = client.get_token_info(&token_id);
+        assert_eq!(info.tokens_sold, 20_000);
+        client.buy_tokens(&alice, &token_id, &1_000_000, &None);
+    }
+
+    #[test]
+    fn test_auction_claim_respects_holding_cap() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let xlm = token::Client::new(&env, &xlm_token);
+
+        let token_id = client.create_token(
+            &Address::generate(&env),
+            &String::from_str(&env, "Fair Token"),
+            &String::from_str(&env, "FAIR"),
+            &1_000_000i128,
+            &10_000_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &Some(LaunchOptions {
+                protection: LaunchProtection {
+                    window_ledgers: 0,
+                    max_buy_per_wallet: None,
+                    max_wallet_percent: Some(1),
+                    max_dev_buy: None,
+                },
+                mode: LaunchMode::Auction(AuctionConfig { duration_ledgers: 10, max_tokens: 20_000 }),
+                creator_allocation: CreatorAllocation::none(),
+                launch_deadline_ledgers: 0,
+                hook: None,
+                hook_trades: false,
+                sell_fee: SellFeeSchedule::none(),
+            }),
+        );
+
+        let alice = funded_account(&env, &xlm_token);
+        let bob = funded_account(&env, &xlm_token);
+        client.commit_auction(&alice, &token_id, &10_000_000);
+        client.commit_auction(&bob, &token_id, &30_000_000);
+        env.ledger().with_mut(|li| li.sequence_number += 10);
+        client.settle_auction(&token_id);
+
+        // Bob's 15_000 token allocation is capped at 1% of supply; the rest goes back to the curve
+        let claim = client.claim_auction(&bob, &token_id);
+        assert_eq!(claim, AuctionClaim { tokens_received: 10_000, quote_refunded: 19_100_000 });
+        assert_eq!(client.get_balance(&token_id, &bob), 10_000);
+
+        let claim = client.claim_auction(&alice, &token_id);
+        assert_eq!(claim, AuctionClaim { tokens_received: 5_000, quote_refunded: 4_550_000 });
+
+        let info = client.get_token_info(&token_id);
+        assert_eq!(info.tokens_sold, 15_000);
+        assert_eq!(info.quote_raised, 16_350_000);
+        assert_eq!(xlm.balance(&client.address), info.quote_raised);
+    }
+
+    #[test]
+    fn test_commit_reveal_buy() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let xlm = token::Client::new(&env, &xlm_token);
+        let buyer = funded_account(&env, &xlm_token);
+        let starting_balance = xlm.balance(&buyer);
+
+        // Escrow 2 XLM for a hidden 1 XLM order
+        let salt = BytesN::from_array(&env, &[42u8; 32]);
+        let hash = CommitReveal::commitment_hash(&env, &buyer, &token_id, 10_000_000, 9_000, &salt);
+        client.commit_buy(&buyer, &token_id, &hash, &20_000_000);
+        assert_eq!(xlm.balance(&buyer), starting_balance - 20_000_000);
+
+        // Revealing in the commit ledger or with different details fails
+        let result = client.try_reveal_buy(&buyer, &token_id, &10_000_000, &9_000, &salt, &None);
+        assert_eq!(result, Err(Ok(ContractError::RevealTooEarly)));
+
+        env.ledger().with_mut(|li| li.sequence_number += 1);
+        let result = client.try_reveal_buy(&buyer, &token_id, &10_000_000, &1, &salt, &None);
+        assert_eq!(result, Err(Ok(ContractError::CommitmentNotFound)));
+
+        let result = client.reveal_buy(&buyer, &token_id, &10_000_000, &9_000, &salt, &None);
+        assert_eq!(result.tokens_received, 9_586);
+        assert_eq!(xlm.balance(&buyer), starting_balance - 10_000_000);
+        assert_eq!(client.get_buy_commitment(&buyer, &hash), None);
+    }
+
+    #[test]
+    fn test_reveal_respects_min_output() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let buyer = funded_account(&env, &xlm_token);
+
+        let salt = BytesN::from_array(&env, &[1u8; 32]);
+        let hash = CommitReveal::commitment_hash(&env, &buyer, &token_id, 10_000_000, 20_000, &salt);
+        client.commit_buy(&buyer, &token_id, &hash, &10_000_000);
+
+        env.ledger().with_mut(|li| li.sequence_number += 1);
+        let result = client.try_reveal_buy(&buyer, &token_id, &10_000_000, &20_000, &salt, &None);
+        assert_eq!(result, Err(Ok(ContractError::MinimumNotMet)));
+    }
+
+    #[test]
+    fn test_reclaim_unrevealed_commitment() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let xlm = token::Client::new(&env, &xlm_token);
+        let buyer = funded_account(&env, &xlm_token);
+        let starting_balance = xlm.balance(&buyer);
+
+        let hash = BytesN::from_array(&env, &[9u8; 32]);
+        client.commit_buy(&buyer, &token_id, &hash, &5_000_000);
+        let result = client.try_commit_buy(&buyer, &token_id, &hash, &5_000_000);
+        assert_eq!(result, Err(Ok(ContractError::CommitmentExists)));
+
+        // The escrow stays locked while the commitment can still be revealed
+        assert_eq!(client.try_reclaim_buy(&buyer, &hash), Err(Ok(ContractError::RevealWindowOpen)));
+
+        env.ledger().with_mut(|li| li.sequence_number += 121);
+        assert_eq!(client.reclaim_buy(&buyer, &hash), 5_000_000);
+        assert_eq!(xlm.balance(&buyer), starting_balance);
+    }
+
+    #[test]
+    fn test_commitment_cannot_be_squatted() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let buyer = funded_account(&env, &xlm_token);
+        let attacker = funded_account(&env, &xlm_token);
+
+        // An attacker copying a pending hash doesn't block the buyer's commitment
+        let salt = BytesN::from_array(&env, &[42u8; 32]);
+        let hash = CommitReveal::commitment_hash(&env, &buyer, &token_id, 10_000_000, 0, &salt);
+        client.commit_buy(&attacker, &token_id, &hash, &10_000_000);
+        client.commit_buy(&buyer, &token_id, &hash, &10_000_000);
+
+        // Nor can the attacker reveal the buyer's order details as their own
+        env.ledger().with_mut(|li| li.sequence_number += 1);
+        let result = client.try_reveal_buy(&attacker, &token_id, &10_000_000, &0, &salt, &None);
+        assert_eq!(result, Err(Ok(ContractError::CommitmentNotFound)));
+
+        client.reveal_buy(&buyer, &token_id, &10_000_000, &0, &salt, &None);
+        assert_eq!(client.get_buy_commitment(&buyer, &hash), None);
+        assert_eq!(client.get_buy_commitment(&attacker, &hash).unwrap().escrowed_xlm, 10_000_000);
+    }
+
+    #[test]
+    fn test_buy_exact_tokens() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let xlm = token::Client::new(&env, &xlm_token);
+        let buyer = funded_account(&env, &xlm_token);
+        let fee_recipient = Address::generate(&env);
+        client.set_fee_config(&100, &fee_recipient);
+
+        // 10_000 tokens cost 1000 * 10_000 + 9000 * 10_000^2 / 2_000_000, paid in net of a 1% fee
+        let result = client.try_buy_exact_tokens(&buyer, &token_id, &10_000, &10_555_554, &None);
+        assert_eq!(result, Err(Ok(ContractError::MaximumExceeded)));
+
+        let starting_balance = xlm.balance(&buyer);
+        let result = client.buy_exact_tokens(&buyer, &token_id, &10_000, &10_555_555, &None);
+        assert_eq!(result.tokens_received, 10_000);
+        assert_eq!(result.quote_spent, 10_555_555);
+        assert_eq!(xlm.balance(&buyer), starting_balance - 10_555_555);
+        assert_eq!(xlm.balance(&fee_recipient), 105_555);
+        assert_eq!(client.get_balance(&token_id, &buyer), 10_000);
+        assert_eq!(client.get_token_info(&token_id).quote_raised, 10_450_000);
+
+        // Buying more than the curve has left is rejected
+        let result = client.try_buy_exact_tokens(&buyer, &token_id, &990_001, &i128::MAX, &None);
+        assert_eq!(result, Err(Ok(ContractError::InsufficientSupply)));
+    }
+
+    #[test]
+    fn test_buy_paths_price_alike() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let (other_client, _, other_token, other_xlm) = create_protected_token(&env, LaunchProtection::none());
+        let buyer = funded_account(&env, &xlm_token);
+        let other_buyer = funded_account(&env, &other_xlm);
+        let fee_recipient = Address::generate(&env);
+        let other_fee_recipient = Address::generate(&env);
+        client.set_fee_config(&100, &fee_recipient);
+        other_client.set_fee_config(&100, &other_fee_recipient);
+
+        // Spending what an exact buy cost on an identical curve buys the same tokens for the same fee
+        let spent = client.buy_exact_tokens(&buyer, &token_id, &12_345, &i128::MAX, &None).quote_spent;
+        let result = other_client.buy_tokens(&other_buyer, &other_token, &spent, &None);
+        assert_eq!(result.tokens_received, 12_345);
+        assert_eq!(result.quote_spent, spent);
+        assert_eq!(
+            token::Client::new(&env, &other_xlm).balance(&other_fee_recipient),
+            token::Client::new(&env, &xlm_token).balance(&fee_recipient)
+        );
+        assert_eq!(other_client.get_token_info(&other_token).quote_raised, client.get_token_info(&token_id).quote_raised);
+
+        // And one stroop less buys one token fewer
+        let next = client.buy_exact_tokens(&buyer, &token_id, &1_000, &i128::MAX, &None).quote_spent;
+        let result = other_client.buy_tokens(&other_buyer, &other_token, &(next - 1), &None);
+        assert_eq!(result.tokens_received, 999);
+    }
+
+    #[test]
+    fn test_buy_tokens_for_recipient() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let xlm = token::Client::new(&env, &xlm_token);
+        let payer = funded_account(&env, &xlm_token);
+        let recipient = Address::generate(&env);
+
+        let result = client.try_buy_tokens_for(&payer, &recipient, &token_id, &1_000_000, &i128::MAX);
+        assert_eq!(result, Err(Ok(ContractError::MinimumNotMet)));
+
+        let starting_balance = xlm.balance(&payer);
+        let result = client.buy_tokens_for(&payer, &recipient, &token_id, &1_000_000, &1);
+        assert_eq!(env.auths()[0].0, payer);
+        assert_eq!(xlm.balance(&payer), starting_balance - 1_000_000);
+        assert_eq!(xlm.balance(&recipient), 0);
+        assert_eq!(client.get_balance(&token_id, &payer), 0);
+        assert_eq!(client.get_balance(&token_id, &recipient), result.tokens_received);
+
+        // The recipient owns the tokens outright
+        let sale = client.sell_tokens(&recipient, &token_id, &result.tokens_received, &0, &None);
+        assert!(sale.quote_received > 0);
+        assert_eq!(xlm.balance(&recipient), sale.quote_received);
+    }
+
+    #[test]
+    fn test_platform_fee_on_buys() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let xlm = token::Client::new(&env, &xlm_token);
+        let buyer = funded_account(&env, &xlm_token);
+        let fee_recipient = Address::generate(&env);
+
+        assert_eq!(client.try_set_fee_config(&1001, &fee_recipient), Err(Ok(ContractError::InvalidAmount)));
+        client.set_fee_config(&200, &fee_recipient);
+
+        // 2% of 1 XLM goes to the platform, the rest buys from the curve
+        let result = client.buy_tokens(&buyer, &token_id, &10_000_000, &None);
+        assert_eq!(result.tokens_received, 9_402);
+        assert_eq!(xlm.balance(&fee_recipient), 200_000);
+        assert_eq!(xlm.balance(&client.address), 9_800_000);
+    }
+
+    #[test]
+    fn test_quote_asset_curve() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let xlm = token::Client::new(&env, &xlm_token);
+        let usdc_token = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        let usdc = token::Client::new(&env, &usdc_token);
+        let fee_recipient = Address::generate(&env);
+        client.set_fee_config(&100, &fee_recipient);
+
+        // Launch once 50 USDC has been raised
+        let token_id = client.create_token(
+            &Address::generate(&env),
+            &String::from_str(&env, "Stable Launch"),
+            &String::from_str(&env, "STBL"),
+            &1_000_000i128,
+            &500_000_000i128,
+            &0u32,
+            &CurveSource::Custom(linear_curve()),
+            &Some(usdc_token.clone()),
+            &None,
+        );
+        let info = client.get_token_info(&token_id);
+        assert_eq!(info.quote_asset, usdc_token);
+        assert_eq!(info.launch_threshold, 500_000_000);
+
+        // Reserves and fees settle in USDC and no XLM moves
+        let buyer = funded_account(&env, &usdc_token);
+        client.buy_tokens(&buyer, &token_id, &10_000_000, &None);
+        assert_eq!(usdc.balance(&client.address), 9_900_000);
+        assert_eq!(usdc.balance(&fee_recipient), 100_000);
+        assert_eq!(xlm.balance(&client.address), 0);
+        assert_eq!(client.get_token_info(&token_id).quote_raised, 9_900_000);
+
+        // A buyer holding only XLM cannot pay
+        let xlm_holder = funded_account(&env, &xlm_token);
+        assert!(client.try_buy_tokens(&xlm_holder, &token_id, &10_000_000, &None).is_err());
+
+        // The threshold is measured in USDC
+        let result = client.buy_tokens(&buyer, &token_id, &500_000_000, &None);
+        assert!(result.launch_triggered);
+    }
+
+    #[test]
+    fn test_sell_tokens() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let xlm = token::Client::new(&env, &xlm_token);
+        let trader = funded_account(&env, &xlm_token);
+
+        // 1 XLM buys as many tokens as the curve integral allows
+        client.buy_tokens(&trader, &token_id, &10_000_000, &None);
+        assert_eq!(client.get_balance(&token_id, &trader), 9_586);
+
+        let result = client.try_sell_tokens(&trader, &token_id, &9_587, &0, &None);
+        assert_eq!(result, Err(Ok(ContractError::InsufficientFunds)));
+
+        // Selling 5_000 refunds the curve integral over that range
+        let result = client.try_sell_tokens(&trader, &token_id, &5_000, &5_318_871, &None);
+        assert_eq!(result, Err(Ok(ContractError::MinimumNotMet)));
+        let starting_balance = xlm.balance(&trader);
+        let result = client.sell_tokens(&trader, &token_id, &5_000, &5_318_870, &None);
+        assert_eq!(result.quote_received, 5_318_870);
+        assert_eq!(xlm.balance(&trader), starting_balance + 5_318_870);
+        assert_eq!(client.get_balance(&token_id, &trader), 4_586);
+
+        // Selling the rest returns the curve to its start, leaving the buy's rounding in the reserve
+        let result = client.sell_tokens(&trader, &token_id, &4_586, &0, &None);
+        assert_eq!(result.quote_received, 4_680_641);
+        assert_eq!(result.new_price, 1000);
+        assert_eq!(client.get_token_info(&token_id).quote_raised, 489);
+        assert_eq!(xlm.balance(&client.address), 489);
+    }
+
+    #[test]
+    fn test_round_trip_never_profits() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let xlm = token::Client::new(&env, &xlm_token);
+        let trader = funded_account(&env, &xlm_token);
+
+        // Another holder's reserve is on the line, so the reserve cap can't hide a profit
+        client.buy_tokens(&funded_account(&env, &xlm_token), &token_id, &50_000_000, &None);
+
+        for amount in [2_000, 999_999, 10_000_000, 33_333_333] {
+            let starting_balance = xlm.balance(&trader);
+            let bought = client.buy_tokens(&trader, &token_id, &amount, &None).tokens_received;
+            client.sell_tokens(&trader, &token_id, &bought, &0, &None);
+            assert!(xlm.balance(&trader) <= starting_balance);
+        }
+    }
+
+    #[test]
+    fn test_swap_round_trip_never_profits() {
+        let env = Env::default();
+        let (client, _, token_a, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let trader = funded_account(&env, &xlm_token);
+        let token_b = client.create_token(
+            &trader,
+            &String::from_str(&env, "Second Token"),
+            &String::from_str(&env, "NEXT"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &None,
+        );
+
+        // Other holders' reserves are on the line in both curves
+        client.buy_tokens(&funded_account(&env, &xlm_token), &token_a, &50_000_000, &None);
+        client.buy_tokens(&funded_account(&env, &xlm_token), &token_b, &50_000_000, &None);
+
+        for amount in [2_000, 999_999, 10_000_000, 33_333_333] {
+            let starting_tokens = client.buy_tokens(&trader, &token_a, &amount, &None).tokens_received;
+            let swapped = client.swap(&trader, &token_a, &token_b, &starting_tokens, &0, &None).tokens_received;
+            let returned = client.swap(&trader, &token_b, &token_a, &swapped, &0, &None).tokens_received;
+            assert!(returned <= starting_tokens);
+            client.sell_tokens(&trader, &token_a, &returned, &0, &None);
+        }
+    }
+
+    #[test]
+    fn test_swap_registers_referrer() {
+        let env = Env::default();
+        let (client, _, token_a, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let fee_recipient = Address::generate(&env);
+        let referrer = Address::generate(&env);
+        let trader = funded_account(&env, &xlm_token);
+        let token_b = client.create_token(
+            &trader,
+            &String::from_str(&env, "Second Token"),
+            &String::from_str(&env, "NEXT"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &None,
+        );
+        client.set_fee_config(&100, &fee_recipient);
+        client.set_referral_share(&5000);
+
+        // A trader whose first trade is a swap can still name a referrer
+        let tokens = client.buy_tokens(&trader, &token_a, &10_000_000, &None).tokens_received;
+        assert_eq!(client.get_referral_rewards(&referrer, &xlm_token), 0);
+        client.swap(&trader, &token_a, &token_b, &tokens, &0, &Some(referrer.clone()));
+        assert!(client.get_referral_rewards(&referrer, &xlm_token) > 0);
+
+        let result = client.try_swap(&trader, &token_b, &token_a, &1_000, &0, &Some(Address::generate(&env)));
+        assert_eq!(result, Err(Ok(ContractError::ReferrerMismatch)));
+    }
+
+    #[test]
+    fn test_swap_between_curves() {
+        let env = Env::default();
+        let (client, _, from_token, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let xlm = token::Client::new(&env, &xlm_token);
+        let trader = funded_account(&env, &xlm_token);
+        let to_token = client.create_token(
+            &trader,
+            &String::from_str(&env, "Second Token"),
+            &String::from_str(&env, "NEXT"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &None,
+        );
+        let fee_recipient = Address::generate(&env);
+        client.set_fee_config(&100, &fee_recipient);
+
+        let bought = client.buy_tokens(&trader, &from_token, &10_000_000, &None).tokens_received;
+        let trader_balance = xlm.balance(&trader);
+        let contract_balance = xlm.balance(&client.address);
+        let fees_before = xlm.balance(&fee_recipient);
+
+        assert_eq!(
+            client.try_swap(&trader, &from_token, &from_token, &bought, &0, &None),
+            Err(Ok(ContractError::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_swap(&trader, &from_token, &to_token, &bought, &i128::MAX, &None),
+            Err(Ok(ContractError::MinimumNotMet))
+        );
+
+        let result = client.swap(&trader, &from_token, &to_token, &bought, &1, &None);
+        assert_eq!(client.get_balance(&from_token, &trader), 0);
+        assert_eq!(client.get_balance(&to_token, &trader), result.tokens_received);
+
+        // Only the sale pays the fee, and the rest of the quote stays in the contract
+        let fee = xlm.balance(&fee_recipient) - fees_before;
+        assert!(fee > 0);
+        assert_eq!(xlm.balance(&trader), trader_balance);
+        assert_eq!(xlm.balance(&client.address), contract_balance - fee);
+        assert_eq!(client.get_token_info(&to_token).quote_raised, result.quote_spent);
+        assert_eq!(
+            client.get_token_info(&from_token).quote_raised,
+            contract_balance - result.quote_spent - fee
+        );
+    }
+
+    #[test]
+    fn test_limit_orders() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let xlm = token::Client::new(&env, &xlm_token);
+        let alice = funded_account(&env, &xlm_token);
+        let bob = funded_account(&env, &xlm_token);
+        let keeper = Address::generate(&env);
+        let held = client.buy_tokens(&alice, &token_id, &10_000_000, &None).tokens_received;
+        let price = client.get_current_price(&token_id);
+
+        // Alice sells half if the price climbs 10%, Bob buys if it falls back to the current price
+        let sell_order = client.place_order(&token_id, &LimitOrder {
+            owner: alice.clone(),
+            side: OrderSide::Sell,
+            amount: held / 2,
+            trigger_price: price * 11 / 10,
+            min_out: 0,
+            executor_fee: 10_000,
+            expiry_ledger: env.ledger().sequence() + 100,
+        });
+        assert_eq!(client.get_balance(&token_id, &alice), held - held / 2);
+        let buy_order = client.place_order(&token_id, &LimitOrder {
+            owner: bob.clone(),
+            side: OrderSide::Buy,
+            amount: 5_000_000,
+            trigger_price: price,
+            min_out: 1,
+            executor_fee: 10_000,
+            expiry_ledger: env.ledger().sequence() + 100,
+        });
+
+        let result = client.try_execute_order(&keeper, &token_id, &sell_order);
+        assert_eq!(result, Err(Ok(ContractError::TriggerNotReached)));
+
+        // A large buy lifts the price past Alice's trigger and above Bob's
+        let bought = client.buy_tokens(&bob, &token_id, &20_000_000, &None).tokens_received;
+        let result = client.try_execute_order(&keeper, &token_id, &buy_order);
+        assert_eq!(result, Err(Ok(ContractError::TriggerNotReached)));
+
+        let alice_xlm = xlm.balance(&alice);
+        let payout = client.execute_order(&keeper, &token_id, &sell_order);
+        assert_eq!(xlm.balance(&alice), alice_xlm + payout);
+        assert_eq!(xlm.balance(&keeper), 10_000);
+        assert_eq!(client.get_limit_order(&token_id, &sell_order), None);
+        let result = client.try_execute_order(&keeper, &token_id, &sell_order);
+        assert_eq!(result, Err(Ok(ContractError::OrderNotFound)));
+
+        // Once the price falls back, Bob's escrow buys at the curve price
+        client.sell_tokens(&bob, &token_id, &bought, &0, &None);
+        assert!(client.get_current_price(&token_id) <= price);
+        let bob_tokens = client.get_balance(&token_id, &bob);
+        let filled = client.execute_order(&keeper, &token_id, &buy_order);
+        assert_eq!(client.get_balance(&token_id, &bob), bob_tokens + filled);
+        assert_eq!(xlm.balance(&keeper), 20_000);
+
+        // Orders can be cancelled for their escrow, and lapse at their deadline
+        let order = LimitOrder {
+            owner: alice.clone(),
+            side: OrderSide::Buy,
+            amount: 1_000_000,
+            trigger_price: price * 2,
+            min_out: 0,
+            executor_fee: 0,
+            expiry_ledger: env.ledger().sequence() + 10,
+        };
+        let alice_xlm = xlm.balance(&alice);
+        let order_id = client.place_order(&token_id, &order);
+        assert_eq!(xlm.balance(&alice), alice_xlm - 1_000_000);
+        client.cancel_order(&token_id, &order_id);
+        assert_eq!(xlm.balance(&alice), alice_xlm);
+
+        let order_id = client.place_order(&token_id, &order);
+        env.ledger().with_mut(|li| li.sequence_number += 11);
+        let result = client.try_execute_order(&keeper, &token_id, &order_id);
+        assert_eq!(result, Err(Ok(ContractError::OrderExpired)));
+        client.cancel_order(&token_id, &order_id);
+        assert_eq!(xlm.balance(&alice), alice_xlm);
+    }
+
+    fn create_surcharged_token(
+        env: &Env,
+        client: &LaunchpadContractClient,
+        symbol: &str,
+        to_reserve: bool,
+    ) -> String {
+        client.create_token(
+            &Address::generate(env),
+            &String::from_str(env, "Surcharged Token"),
+            &String::from_str(env, symbol),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &Some(LaunchOptions {
+                protection: LaunchProtection::none(),
+                mode: LaunchMode::Open,
+                creator_allocation: CreatorAllocation::none(),
+                launch_deadline_ledgers: 0,
+                hook: None,
+                hook_trades: false,
+                sell_fee: SellFeeSchedule {
+                    max_surcharge_bps: 2000,
+                    decay_ledgers: 100,
+                    to_reserve,
+                },
+            }),
+        )
+    }
+
+    #[test]
+    fn test_sell_fee_decay() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let xlm = token::Client::new(&env, &xlm_token);
+        let fee_recipient = Address::generate(&env);
+        client.set_fee_config(&0, &fee_recipient);
+        let trader = funded_account(&env, &xlm_token);
+
+        // An immediate sale pays the full surcharge to the fee recipient
+        let token_id = create_surcharged_token(&env, &client, "FLIP", false);
+        let held = client.buy_tokens(&trader, &token_id, &10_000_000, &None).tokens_received;
+        assert_eq!(client.get_holding_ledger(&token_id, &trader), Some(env.ledger().sequence()));
+        let raised = client.get_token_info(&token_id).quote_raised;
+        let payout = client.sell_tokens(&trader, &token_id, &held, &0, &None).quote_received;
+        let surcharge = xlm.balance(&fee_recipient);
+        assert!(surcharge > 0);
+        let dust = client.get_token_info(&token_id).quote_raised;
+        assert_eq!(payout + surcharge + dust, raised);
+
+        // Halfway through the decay it is halved, and stays in the reserve
+        let token_id = create_surcharged_token(&env, &client, "HOLD", true);
+        let held = client.buy_tokens(&trader, &token_id, &10_000_000, &None).tokens_received;
+        env.ledger().with_mut(|li| li.sequence_number += 50);
+        let payout = client.sell_tokens(&trader, &token_id, &held, &0, &None).quote_received;
+        assert_eq!(xlm.balance(&fee_recipient), surcharge);
+        assert_eq!(client.get_token_info(&token_id).quote_raised, dust + surcharge / 2);
+        assert_eq!(payout + surcharge / 2 + dust, raised);
+
+        // Topping up moves the holding clock to the weighted average
+        let start = env.ledger().sequence();
+        client.buy_tokens(&trader, &token_id, &10_000_000, &None);
+        env.ledger().with_mut(|li| li.sequence_number += 100);
+        client.buy_tokens(&trader, &token_id, &10_000_000, &None);
+        let since = client.get_holding_ledger(&token_id, &trader).unwrap();
+        assert!(since > start && since <= start + 50);
+
+        // Once fully decayed only the platform fee applies
+        env.ledger().with_mut(|li| li.sequence_number += 100);
+        let held = client.get_balance(&token_id, &trader);
+        let raised = client.get_token_info(&token_id).quote_raised;
+        let payout = client.sell_tokens(&trader, &token_id, &held, &0, &None).quote_received;
+        assert_eq!(client.get_token_info(&token_id).quote_raised, raised - payout);
+        assert_eq!(xlm.balance(&fee_recipient), surcharge);
+
+        // Schedules must decay and stay within the cap
+        assert_eq!(client.get_sell_fee(&String::from_str(&env, "HOLD")).unwrap().decay_ledgers, 100);
+        let result = client.try_create_token(
+            &trader,
+            &String::from_str(&env, "Bad Schedule"),
+            &String::from_str(&env, "BAD"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &Some(LaunchOptions {
+                protection: LaunchProtection::none(),
+                mode: LaunchMode::Open,
+                creator_allocation: CreatorAllocation::none(),
+                launch_deadline_ledgers: 0,
+                hook: None,
+                hook_trades: false,
+                sell_fee: SellFeeSchedule {
+                    max_surcharge_bps: 2000,
+                    decay_ledgers: 0,
+                    to_reserve: true,
+                },
+            }),
+        );
+        assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+    }
+
+    #[test]
+    fn test_time_weighted_average_price() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let trader = funded_account(&env, &xlm_token);
+
+        // The opening price holds for the first 100 seconds
+        env.ledger().with_mut(|li| li.timestamp += 100);
+        assert_eq!(client.get_twap(&token_id, &100), 1000);
+
+        client.buy_tokens(&trader, &token_id, &10_000_000, &None);
+        let price = client.get_current_price(&token_id);
+        assert!(price > 1000);
+
+        env.ledger().with_mut(|li| li.timestamp += 100);
+        assert_eq!(client.get_twap(&token_id, &100), price);
+        assert_eq!(client.get_twap(&token_id, &200), (1000 + price) / 2);
+
+        // Windows must be non-empty and covered by recorded history
+        assert_eq!(client.try_get_twap(&token_id, &0), Err(Ok(ContractError::InvalidAmount)));
+        assert_eq!(client.try_get_twap(&token_id, &201), Err(Ok(ContractError::InvalidAmount)));
+    }
+
+    #[test]
+    fn test_curve_presets() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let creator = funded_account(&env, &xlm_token);
+        let mut preset = CurvePreset {
+            name: String::from_str(&env, "Standard"),
+            curve_params: linear_curve(),
+            total_supply: 2_000_000,
+            launch_threshold: 50_000_000,
+            launch_threshold_percent: 0,
+        };
+        client.set_curve_preset(&1, &Some(preset.clone()));
+        assert_eq!(client.get_curve_preset(&1), Some(preset.clone()));
+
+        // Zero arguments take the preset's supply and thresholds
+        let token_id = client.create_token(
+            &creator,
+            &String::from_str(&env, "Preset Token"),
+            &String::from_str(&env, "PRESET"),
+            &0i128,
+            &0i128,
+            &0u32,
+            &CurveSource::Preset(1),
+            &None,
+            &None,
+        );
+        let token_info = client.get_token_info(&token_id);
+        assert_eq!(token_info.total_supply, 2_000_000);
+        assert_eq!(token_info.launch_threshold, 50_000_000);
+        assert_eq!(token_info.curve_params, linear_curve());
+
+        // Retuning a preset applies to later launches only
+        preset.curve_params.base_price = 2000;
+        client.set_curve_preset(&1, &Some(preset.clone()));
+        let token_id = client.create_token(
+            &creator,
+            &String::from_str(&env, "Retuned Token"),
+            &String::from_str(&env, "RETUNE"),
+            &1_000_000i128,
+            &0i128,
+            &0u32,
+            &CurveSource::Preset(1),
+            &None,
+            &None,
+        );
+        let token_info = client.get_token_info(&token_id);
+        assert_eq!(token_info.total_supply, 1_000_000);
+        assert_eq!(token_info.current_price, 2000);
+        assert_eq!(client.get_token_info(&String::from_str(&env, "PRESET")).current_price, 1000);
+
+        preset.launch_threshold = 0;
+        let result = client.try_set_curve_preset(&2, &Some(preset));
+        assert_eq!(result, Err(Ok(ContractError::InvalidLaunchThreshold)));
+
+        client.set_curve_preset(&1, &None);
+        assert_eq!(client.get_curve_presets().len(), 0);
+        let result = client.try_set_curve_preset(&1, &None);
+        assert_eq!(result, Err(Ok(ContractError::InvalidCurveParameters)));
+        let result = client.try_create_token(
+            &creator,
+            &String::from_str(&env, "Missing Preset"),
+            &String::from_str(&env, "MISSING"),
+            &0i128,
+            &0i128,
+            &0u32,
+            &CurveSource::Preset(1),
+            &None,
+            &None,
+        );
+        assert_eq!(result, Err(Ok(ContractError::InvalidCurveParameters)));
+    }
+
+    #[test]
+    fn test_token_metadata() {
+        let env = Env::default();
+        let (client, creator, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        assert_eq!(client.get_token_metadata(&token_id), None);
+
+        let mut metadata = TokenMetadata {
+            image_uri: String::from_str(&env, "ipfs://bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"),
+            description: String::from_str(&env, "Safe token"),
+            website: String::from_str(&env, "https://safe.example"),
+            socials: vec![&env, String::from_str(&env, "https://x.com/safe")],
+            content_hash: BytesN::from_array(&env, &[7; 32]),
+        };
+        client.set_token_metadata(&token_id, &metadata);
+        assert_eq!(env.auths()[0].0, creator);
+        assert_eq!(client.get_token_metadata(&token_id), Some(metadata.clone()));
+
+        // The creator can keep editing until graduation
+        metadata.description = String::from_str(&env, "Safe token, now with a roadmap");
+        client.set_token_metadata(&token_id, &metadata);
+        assert_eq!(client.get_token_metadata(&token_id), Some(metadata.clone()));
+
+        metadata.description = String::from_bytes(&env, &[b'a'; 1025]);
+        let result = client.try_set_token_metadata(&token_id, &metadata);
+        assert_eq!(result, Err(Ok(ContractError::InvalidMetadata)));
+
+        // Graduation freezes it
+        let buyer = funded_account(&env, &xlm_token);
+        client.buy_tokens(&buyer, &token_id, &100_000_000, &None);
+        assert!(client.get_token_info(&token_id).is_launched);
+        metadata.description = String::from_str(&env, "Changed after graduation");
+        let result = client.try_set_token_metadata(&token_id, &metadata);
+        assert_eq!(result, Err(Ok(ContractError::AlreadyLaunched)));
+        assert_ne!(client.get_token_metadata(&token_id), Some(metadata));
+    }
+
+    #[test]
+    fn test_referral_rewards() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let xlm = token::Client::new(&env, &xlm_token);
+        let fee_recipient = Address::generate(&env);
+        let referrer = funded_account(&env, &xlm_token);
+        let trader = funded_account(&env, &xlm_token);
+        client.set_fee_config(&100, &fee_recipient);
+        client.set_referral_share(&5000);
+
+        // Half of the 1% fee goes to the referrer
+        client.buy_tokens(&trader, &token_id, &10_000_000, &Some(referrer.clone()));
+        assert_eq!(client.get_referral_rewards(&referrer, &xlm_token), 50_000);
+        assert_eq!(xlm.balance(&fee_recipient), 50_000);
+
+        // Later trades keep crediting the first referrer
+        client.sell_tokens(&trader, &token_id, &1_000, &0, &None);
+        assert_eq!(client.get_referral_rewards(&referrer, &xlm_token), 55_404);
+
+        // Self-referral, referral chains and switching referrers are rejected
+        let result = client.try_buy_tokens(&trader, &token_id, &1_000_000, &Some(trader.clone()));
+        assert_eq!(result, Err(Ok(ContractError::SelfReferral)));
+        let downline = funded_account(&env, &xlm_token);
+        let result = client.try_buy_tokens(&downline, &token_id, &1_000_000, &Some(trader.clone()));
+        assert_eq!(result, Err(Ok(ContractError::ReferralChain)));
+        let result = client.try_buy_tokens(&referrer, &token_id, &1_000_000, &Some(downline.clone()));
+        assert_eq!(result, Err(Ok(ContractError::ReferralChain)));
+        let result = client.try_buy_tokens(&trader, &token_id, &1_000_000, &Some(downline));
+        assert_eq!(result, Err(Ok(ContractError::ReferrerMismatch)));
+
+        let starting_balance = xlm.balance(&referrer);
+        assert_eq!(client.claim_referral_rewards(&referrer, &xlm_token), 55_404);
+        assert_eq!(xlm.balance(&referrer), starting_balance + 55_404);
+        let result = client.try_claim_referral_rewards(&referrer, &xlm_token);
+        assert_eq!(result, Err(Ok(ContractError::NothingToClaim)));
+    }
+
+    #[test]
+    fn test_creator_vesting() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let creator = Address::generate(&env);
+        let buyer = funded_account(&env, &xlm_token);
+
+        let mut options = LaunchOptions {
+            protection: LaunchProtection::none(),
+            mode: LaunchMode::Open,
+            creator_allocation: CreatorAllocation { percent: 11, cliff_ledgers: 100, vesting_ledgers: 1000 },
+            launch_deadline_ledgers: 0,
+            hook: None,
+            hook_trades: false,
+            sell_fee: SellFeeSchedule::none(),
+        };
+        let name = String::from_str(&env, "Vested Token");
+        let symbol = String::from_str(&env, "VEST");
+        let result = client.try_create_token(
+            &creator, &name, &symbol, &1_000_000i128, &100_000_000i128, &80u32, &CurveSource::Custom(linear_curve()), &None, &Some(options.clone()),
+        );
+        assert_eq!(result, Err(Ok(ContractError::InvalidVestingSchedule)));
+
+        // 10% of supply is reserved and held back from the curve
+        options.creator_allocation.percent = 10;
+        let token_id = client.create_token(
+            &creator, &name, &symbol, &1_000_000i128, &100_000_000i128, &80u32, &CurveSource::Custom(linear_curve()), &None, &Some(options),
+        );
+        assert_eq!(client.get_token_info(&token_id).creator_reserved, 100_000);
+        let result = client.try_buy_exact_tokens(&buyer, &token_id, &900_001, &i128::MAX, &None);
+        assert_eq!(result, Err(Ok(ContractError::InsufficientSupply)));
+
+        // Nothing vests before graduation
+        assert_eq!(client.try_claim_vested(&token_id), Err(Ok(ContractError::NotLaunched)));
+        assert!(client.buy_tokens(&buyer, &token_id, &100_000_000, &None).launch_triggered);
+
+        env.ledger().with_mut(|li| li.sequence_number += 99);
+        assert_eq!(client.try_claim_vested(&token_id), Err(Ok(ContractError::NothingToClaim)));
+
+        // Half of the schedule has passed
+        env.ledger().with_mut(|li| li.sequence_number += 401);
+        assert_eq!(client.claim_vested(&token_id), 50_000);
+        let info = client.get_token_info(&token_id);
+        assert_eq!((info.creator_vested, info.creator_claimed), (50_000, 50_000));
+        assert_eq!(client.get_balance(&token_id, &creator), 50_000);
+
+        env.ledger().with_mut(|li| li.sequence_number += 600);
+        assert_eq!(client.claim_vested(&token_id), 50_000);
+        assert_eq!(client.get_balance(&token_id, &creator), 100_000);
+    }
+
+    #[test]
+    fn test_sold_threshold_excludes_creator_reserve() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let creator = Address::generate(&env);
+        let buyer = funded_account(&env, &xlm_token);
+
+        // A 95% threshold is reachable even though 10% of supply is reserved for the creator
+        let options = LaunchOptions {
+            protection: LaunchProtection::none(),
+            mode: LaunchMode::Open,
+            creator_allocation: CreatorAllocation { percent: 10, cliff_ledgers: 0, vesting_ledgers: 1 },
+            launch_deadline_ledgers: 0,
+            hook: None,
+            hook_trades: false,
+            sell_fee: SellFeeSchedule::none(),
+        };
+        let token_id = client.create_token(
+            &creator,
+            &String::from_str(&env, "Reserved Token"),
+            &String::from_str(&env, "RSRV"),
+            &1_000_000i128,
+            &1_000_000_000_000i128,
+            &95u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &Some(options),
+        );
+
+        assert!(!client.buy_exact_tokens(&buyer, &token_id, &854_999, &i128::MAX, &None).launch_triggered);
+        assert!(client.buy_exact_tokens(&buyer, &token_id, &1, &i128::MAX, &None).launch_triggered);
+        assert!(client.get_token_info(&token_id).is_launched);
+    }
+
+    #[test]
+    fn test_merkle_airdrop() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let creator = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        let token_id = client.create_token(
+            &creator,
+            &String::from_str(&env, "Drop Token"),
+            &String::from_str(&env, "DROP"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &Some(LaunchOptions {
+                protection: LaunchProtection::none(),
+                mode: LaunchMode::Open,
+                creator_allocation: CreatorAllocation { percent: 10, cliff_ledgers: 0, vesting_ledgers: 1 },
+                launch_deadline_ledgers: 0,
+                hook: None,
+                hook_trades: false,
+                sell_fee: SellFeeSchedule::none(),
+            }),
+        );
+
+        // Airdrops open after graduation, funded from the creator's vested tokens
+        let leaf_alice = MerkleProof::indexed_leaf_hash(&env, 0, &alice, 10_000);
+        let leaf_bob = MerkleProof::indexed_leaf_hash(&env, 1, &bob, 20_000);
+        let root = MerkleProof::hash_pair(&env, &leaf_alice, &leaf_bob);
+        let expiry_ledger = env.ledger().sequence() + 50;
+        let result = client.try_create_airdrop(&token_id, &30_000, &root, &expiry_ledger);
+        assert_eq!(result, Err(Ok(ContractError::NotLaunched)));
+
+        client.buy_tokens(&funded_account(&env, &xlm_token), &token_id, &100_000_000, &None);
+        env.ledger().with_mut(|li| li.sequence_number += 1);
+        client.claim_vested(&token_id);
+        let airdrop_id = client.create_airdrop(&token_id, &30_000, &root, &expiry_ledger);
+        assert_eq!(client.get_balance(&token_id, &creator), 70_000);
+
+        client.claim_airdrop(&airdrop_id, &0, &alice, &10_000, &vec![&env, leaf_bob.clone()]);
+        assert_eq!(client.get_balance(&token_id, &alice), 10_000);
+        assert!(client.is_airdrop_claimed(&airdrop_id, &0));
+
+        // Each index can be claimed once and amounts must match the tree
+        let result = client.try_claim_airdrop(&airdrop_id, &0, &alice, &10_000, &vec![&env, leaf_bob]);
+        assert_eq!(result, Err(Ok(ContractError::AlreadyClaimed)));
+        let result = client.try_claim_airdrop(&airdrop_id, &1, &bob, &25_000, &vec![&env, leaf_alice.clone()]);
+        assert_eq!(result, Err(Ok(ContractError::InvalidProof)));
+        assert_eq!(client.try_reclaim_airdrop(&airdrop_id), Err(Ok(ContractError::ClaimNotExpired)));
+
+        // Unclaimed tokens return to the creator after expiry
+        env.ledger().with_mut(|li| li.sequence_number = expiry_ledger);
+        let result = client.try_claim_airdrop(&airdrop_id, &1, &bob, &20_000, &vec![&env, leaf_alice]);
+        assert_eq!(result, Err(Ok(ContractError::ClaimExpired)));
+        assert_eq!(client.reclaim_airdrop(&airdrop_id), 20_000);
+        assert_eq!(client.get_balance(&token_id, &creator), 90_000);
+        assert_eq!(client.try_reclaim_airdrop(&airdrop_id), Err(Ok(ContractError::NothingToClaim)));
+    }
+
+    #[test]
+    fn test_keeper_graduation_bounty() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let xlm = token::Client::new(&env, &xlm_token);
+        let keeper = Address::generate(&env);
+        client.set_keeper_bounty(&50);
+
+        let result = client.try_execute_launch_transition(&keeper, &token_id);
+        assert_eq!(result, Err(Ok(ContractError::NotLaunched)));
+
+        // Anyone may graduate a completed curve and earns 0.5% of the reserve
+        client.buy_tokens(&funded_account(&env, &xlm_token), &token_id, &100_000_000, &None);
+        assert_eq!(client.execute_launch_transition(&keeper, &token_id), 500_000);
+        assert_eq!(xlm.balance(&keeper), 500_000);
+        let info = client.get_token_info(&token_id);
+        assert!(info.transition_executed);
+        assert_eq!(info.quote_raised, 99_500_000);
+
+        let result = client.try_execute_launch_transition(&keeper, &token_id);
+        assert_eq!(result, Err(Ok(ContractError::InvalidTokenState)));
+    }
+
+    #[contract]
+    struct RecordingHook;
+
+    #[contractimpl]
+    impl RecordingHook {
+        pub fn on_graduate(env: Env, _token_id: String, quote_raised: i128, _tokens_sold: i128, _price: i128) {
+            env.storage().instance().set(&soroban_sdk::symbol_short!("grad"), &quote_raised);
+        }
+
+        pub fn on_trade(
+            env: Env,
+            _token_id: String,
+            _trader: Address,
+            _is_buy: bool,
+            _token_amount: i128,
+            _quote_amount: i128,
+            _price: i128,
+        ) {
+            let key = soroban_sdk::symbol_short!("trades");
+            let trades: u32 = env.storage().instance().get(&key).unwrap_or(0);
+            env.storage().instance().set(&key, &(trades + 1));
+        }
+
+        pub fn graduated(env: Env) -> Option<i128> {
+            env.storage().instance().get(&soroban_sdk::symbol_short!("grad"))
+        }
+
+        pub fn trades(env: Env) -> u32 {
+            env.storage().instance().get(&soroban_sdk::symbol_short!("trades")).unwrap_or(0)
+        }
+    }
+
+    mod failing_hook {
+        use soroban_sdk::{contract, contractimpl, Env, String};
+
+        #[contract]
+        pub struct FailingHook;
+
+        #[contractimpl]
+        impl FailingHook {
+            pub fn on_graduate(_env: Env, _token_id: String, _quote_raised: i128, _tokens_sold: i128, _price: i128) {
+                panic!("hook failure");
+            }
+        }
+    }
+
+    mod greedy_hook {
+        use soroban_sdk::{contract, contractimpl, Address, Env, String};
+
+        #[contract]
+        pub struct GreedyHook;
+
+        #[contractimpl]
+        impl GreedyHook {
+            pub fn on_trade(
+                env: Env,
+                _token_id: String,
+                _trader: Address,
+                _is_buy: bool,
+                _token_amount: i128,
+                _quote_amount: i128,
+                _price: i128,
+            ) {
+                let mut count = 0u64;
+                loop {
+                    count += 1;
+                    env.storage().instance().set(&count, &count);
+                }
+            }
+        }
+    }
+
+    fn create_hooked_token(
+        env: &Env,
+        client: &LaunchpadContractClient,
+        symbol: &str,
+        hook: &Address,
+        hook_trades: bool,
+    ) -> String {
+        client.create_token(
+            &Address::generate(env),
+            &String::from_str(env, "Hooked Token"),
+            &String::from_str(env, symbol),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &Some(LaunchOptions {
+                protection: LaunchProtection::none(),
+                mode: LaunchMode::Open,
+                creator_allocation: CreatorAllocation::none(),
+                launch_deadline_ledgers: 0,
+                hook: Some(hook.clone()),
+                hook_trades,
+                sell_fee: SellFeeSchedule::none(),
+            }),
+        )
+    }
+
+    #[test]
+    fn test_launch_hooks() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let keeper = Address::generate(&env);
+        let trader = funded_account(&env, &xlm_token);
+
+        // The hook hears about each trade and the graduation once the notices are delivered
+        let hook = RecordingHookClient::new(&env, &env.register(RecordingHook, ()));
+        let token_id = create_hooked_token(&env, &client, "HOOK", &hook.address, true);
+        client.buy_tokens(&trader, &token_id, &10_000_000, &None);
+        client.sell_tokens(&trader, &token_id, &1_000, &0, &None);
+        assert_eq!(client.get_pending_hook_notices(&token_id), 2);
+        assert_eq!(hook.trades(), 0);
+        assert!(client.notify_hook(&token_id));
+        assert!(client.notify_hook(&token_id));
+        assert!(!client.notify_hook(&token_id));
+        assert_eq!(hook.trades(), 2);
+
+        client.buy_tokens(&trader, &token_id, &100_000_000, &None);
+        client.execute_launch_transition(&keeper, &token_id);
+        while client.notify_hook(&token_id) {}
+        assert_eq!(hook.graduated(), Some(client.get_token_info(&token_id).quote_raised));
+        assert_eq!(hook.trades(), 3);
+
+        // A failing hook can't block graduation, and its failure is only reported
+        let failing = env.register(failing_hook::FailingHook, ());
+        let token_id = create_hooked_token(&env, &client, "FAIL", &failing, true);
+        client.buy_tokens(&trader, &token_id, &100_000_000, &None);
+        client.execute_launch_transition(&keeper, &token_id);
+        assert!(client.get_token_info(&token_id).transition_executed);
+        while client.notify_hook(&token_id) {}
+        assert_eq!(client.get_pending_hook_notices(&token_id), 0);
+
+        // The admin can detach a hook
+        client.clear_token_hook(&token_id);
+        assert_eq!(client.get_launch_hook(&token_id), None);
+    }
+
+    #[test]
+    fn test_budget_exhausting_hook() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let keeper = Address::generate(&env);
+        let trader = funded_account(&env, &xlm_token);
+        let greedy = env.register(greedy_hook::GreedyHook, ());
+        let token_id = create_hooked_token(&env, &client, "GRDY", &greedy, true);
+
+        // Trades and graduation never run the hook, so they stay within budget
+        client.buy_tokens(&trader, &token_id, &10_000_000, &None);
+        client.sell_tokens(&trader, &token_id, &1_000, &0, &None);
+        client.buy_tokens(&trader, &token_id, &100_000_000, &None);
+        client.execute_launch_transition(&keeper, &token_id);
+        assert!(client.get_token_info(&token_id).transition_executed);
+
+        // Only the delivery fails, leaving the notice queued
+        let delivery = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| client.notify_hook(&token_id)));
+        assert!(delivery.is_err());
+        env.cost_estimate().budget().reset_default();
+        assert_eq!(client.get_pending_hook_notices(&token_id), 4);
+
+        // Once the admin detaches the hook the queue drains without calling it
+        client.clear_token_hook(&token_id);
+        while client.notify_hook(&token_id) {}
+        assert_eq!(client.get_pending_hook_notices(&token_id), 0);
+    }
+
+    #[test]
+    fn test_expired_launch_refunds() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let xlm = token::Client::new(&env, &xlm_token);
+        let keeper = Address::generate(&env);
+        client.set_keeper_bounty(&50);
+        assert_eq!(client.try_set_keeper_bounty(&101), Err(Ok(ContractError::InvalidAmount)));
+
+        let token_id = client.create_token(
+            &Address::generate(&env),
+            &String::from_str(&env, "Deadline Token"),
+            &String::from_str(&env, "LATE"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &Some(LaunchOptions {
+                protection: LaunchProtection::none(),
+                mode: LaunchMode::Open,
+                creator_allocation: CreatorAllocation::none(),
+                launch_deadline_ledgers: 100,
+                hook: None,
+                hook_trades: false,
+                sell_fee: SellFeeSchedule::none(),
+            }),
+        );
+
+        let alice = funded_account(&env, &xlm_token);
+        let bob = funded_account(&env, &xlm_token);
+        client.buy_tokens(&alice, &token_id, &5_000_000, &None);
+        client.buy_tokens(&bob, &token_id, &5_000_000, &None);
+        assert_eq!(client.get_balance(&token_id, &bob), 4_694);
+
+        let result = client.try_activate_refunds(&keeper, &token_id);
+        assert_eq!(result, Err(Ok(ContractError::InvalidTokenState)));
+
+        // Past the deadline a keeper opens refunds and trading stops
+        env.ledger().with_mut(|li| li.sequence_number += 100);
+        assert_eq!(client.activate_refunds(&keeper, &token_id), 50_000);
+        let result = client.try_buy_tokens(&alice, &token_id, &1_000_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::InvalidTokenState)));
+
+        // Holders split the remaining reserve pro rata
+        let alice_before = xlm.balance(&alice);
+        assert_eq!(client.claim_refund(&alice, &token_id), 5_077_759);
+        assert_eq!(xlm.balance(&alice), alice_before + 5_077_759);
+        assert_eq!(client.claim_refund(&bob, &token_id), 4_872_241);
+        assert_eq!(client.try_claim_refund(&bob, &token_id), Err(Ok(ContractError::NothingToClaim)));
+        assert_eq!(xlm.balance(&client.address), 0);
+    }
+
+    #[test]
+    fn test_emergency_shutdown() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let xlm = token::Client::new(&env, &xlm_token);
+        let alice = funded_account(&env, &xlm_token);
+        let bob = funded_account(&env, &xlm_token);
+        client.buy_tokens(&alice, &token_id, &5_000_000, &None);
+        client.buy_tokens(&bob, &token_id, &5_000_000, &None);
+
+        // A second token graduates before the shutdown, with its launch transition still pending
+        let graduated_id = client.create_token(
+            &alice,
+            &String::from_str(&env, "Graduated Token"),
+            &String::from_str(&env, "GRAD"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &None,
+        );
+        client.buy_tokens(&bob, &graduated_id, &100_000_000, &None);
+        assert!(client.get_token_info(&graduated_id).is_launched);
+
+        assert_eq!(client.try_claim_refund(&alice, &token_id), Err(Ok(ContractError::InvalidTokenState)));
+        client.emergency_shutdown();
+        assert!(client.is_shutdown());
+        assert_eq!(client.try_emergency_shutdown(), Err(Ok(ContractError::PlatformShutdown)));
+
+        // All trading stops permanently
+        let result = client.try_buy_tokens(&alice, &token_id, &1_000_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::PlatformShutdown)));
+        let result = client.try_sell_tokens(&alice, &token_id, &1_000, &0, &None);
+        assert_eq!(result, Err(Ok(ContractError::PlatformShutdown)));
+
+        // Holders of the non-graduated token split its reserve pro rata
+        assert_eq!(client.claim_refund(&alice, &token_id), 5_103_275);
+        assert_eq!(client.claim_refund(&bob, &token_id), 4_896_725);
+        assert_eq!(client.get_balance(&token_id, &alice), 0);
+        assert_eq!(xlm.balance(&client.address), 100_000_000);
+
+        // Graduated tokens are not part of the exit
+        assert_eq!(client.try_claim_refund(&bob, &graduated_id), Err(Ok(ContractError::InvalidTokenState)));
+    }
+
+    #[test]
+    fn test_lock_token_creation() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let trader = funded_account(&env, &xlm_token);
+        assert!(!client.is_creation_locked());
+
+        // Existing tokens keep trading; only new tokens are refused
+        client.lock_token_creation();
+        assert!(client.is_creation_locked());
+        let result = client.try_create_token(
+            &trader,
+            &String::from_str(&env, "Second Token"),
+            &String::from_str(&env, "NEXT"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &None,
+        );
+        assert_eq!(result, Err(Ok(ContractError::CreationLocked)));
+        client.buy_tokens(&trader, &token_id, &1_000_000, &None);
+    }
+
+    #[test]
+    fn test_shutdown_after_graduation() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let xlm = token::Client::new(&env, &xlm_token);
+        let creator = funded_account(&env, &xlm_token);
+        let buyer = funded_account(&env, &xlm_token);
+        let alice = Address::generate(&env);
+
+        let token_id = client.create_token(
+            &creator,
+            &String::from_str(&env, "Vested Token"),
+            &String::from_str(&env, "VEST"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &Some(LaunchOptions {
+                protection: LaunchProtection::none(),
+                mode: LaunchMode::Open,
+                creator_allocation: CreatorAllocation { percent: 10, cliff_ledgers: 0, vesting_ledgers: 1 },
+                launch_deadline_ledgers: 0,
+                hook: None,
+                hook_trades: false,
+                sell_fee: SellFeeSchedule::none(),
+            }),
+        );
+
+        // The creator buys on the curve, then vests and airdrops part of the allocation
+        let bought = client.buy_tokens(&creator, &token_id, &5_000_000, &None).tokens_received;
+        assert!(client.buy_tokens(&buyer, &token_id, &100_000_000, &None).launch_triggered);
+        env.ledger().with_mut(|li| li.sequence_number += 1);
+        assert_eq!(client.claim_vested(&token_id), 100_000);
+        let leaf = MerkleProof::indexed_leaf_hash(&env, 0, &alice, 30_000);
+        let expiry_ledger = env.ledger().sequence() + 50;
+        let airdrop_id = client.create_airdrop(&token_id, &30_000, &leaf, &expiry_ledger);
+        client.claim_airdrop(&airdrop_id, &0, &alice, &30_000, &Vec::new(&env));
+
+        // Vesting and airdrops only follow graduation, which also closes the shutdown exit
+        client.emergency_shutdown();
+        for holder in [&creator, &buyer, &alice] {
+            assert_eq!(client.try_claim_refund(holder, &token_id), Err(Ok(ContractError::InvalidTokenState)));
+        }
+        assert_eq!(client.get_balance(&token_id, &creator), bought + 70_000);
+        assert_eq!(xlm.balance(&client.address), client.get_token_info(&token_id).quote_raised);
+    }
+
+    #[test]
+    fn test_multisig_timelock_governance() {
+        let env = Env::default();
+        let (client, _, token_id, xlm_token) = create_protected_token(&env, LaunchProtection::none());
+        let trader = funded_account(&env, &xlm_token);
+        let admins = vec![&env, Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+        let (alice, bob, carol) = (admins.get(0).unwrap(), admins.get(1).unwrap(), admins.get(2).unwrap());
+        let result = client.try_configure_governance(&Governance { admins: admins.clone(), threshold: 2, delay_ledgers: 0 });
+        assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+        client.configure_governance(&Governance { admins, threshold: 2, delay_ledgers: 720 });
+
+        // The single admin key no longer acts alone
+        let fee_config = FeeConfig { fee_bps: 200, fee_recipient: Address::generate(&env) };
+        let result = client.try_set_fee_config(&fee_config.fee_bps, &fee_config.fee_recipient);
+        assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+        let result = client.try_propose_action(&trader, &AdminAction::Shutdown);
+        assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+        // 2-of-3 approvals and the timelock are both required
+        let action_id = client.propose_action(&alice, &AdminAction::SetFeeConfig(fee_config.clone()));
+        assert_eq!(client.get_queued_action(&action_id).unwrap().eta_ledger, env.ledger().sequence() + 720);
+        assert_eq!(client.try_execute_action(&action_id), Err(Ok(ContractError::ApprovalsMissing)));
+        client.approve_action(&bob, &action_id);
+        assert_eq!(client.try_execute_action(&action_id), Err(Ok(ContractError::TimelockActive)));
+        env.ledger().with_mut(|li| li.sequence_number += 720);
+        client.execute_action(&action_id);
+        assert_eq!(client.get_fee_config(), Some(fee_config));
+        assert_eq!(client.get_queued_action(&action_id), None);
+
+        // Any admin can cancel a queued action during the delay
+        let action_id = client.propose_action(&alice, &AdminAction::Shutdown);
+        client.approve_action(&bob, &action_id);
+        client.cancel_action(&carol, &action_id);
+        assert_eq!(client.try_execute_action(&action_id), Err(Ok(ContractError::ActionNotFound)));
+        assert!(!client.is_shutdown());
+
+        // Pause is immediate, resuming goes through the queue
+        client.pause(&carol);
+        let result = client.try_buy_tokens(&trader, &token_id, &1_000_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::Paused)));
+        assert_eq!(client.try_unpause(), Err(Ok(ContractError::Unauthorized)));
+
+        let action_id = client.propose_action(&bob, &AdminAction::Unpause);
+        client.approve_action(&carol, &action_id);
+        env.ledger().with_mut(|li| li.sequence_number += 720);
+        client.execute_action(&action_id);
+        assert!(!client.is_paused());
+        client.buy_tokens(&trader, &token_id, &1_000_000, &None);
+    }
+
+    #[test]
+    fn test_migrate_legacy_storage() {
+        let env = Env::default();
+        let (client, xlm_token) = setup_launchpad(&env);
+        let creator = Address::generate(&env);
+        let token_address = Address::generate(&env);
+        let launched_id = String::from_str(&env, "OLD");
+        let open_id = String::from_str(&env, "OPEN");
+        let legacy_token = |symbol: &String, is_launched: bool| storage::legacy::TokenInfo {
+            name: String::from_str(&env, "Old Token"),
+            symbol: symbol.clone(),
+            total_supply: 1_000_000,
+            tokens_sold: 99_000,
+            xlm_raised: 104_000_000,
+            current_price: 1_104,
+            launch_progress_percent: 9,
+            is_launched,
+            creator: creator.clone(),
+            creation_time: 1234567890,
+        };
+        let legacy_state = storage::legacy::LaunchpadState {
+            token_address: token_address.clone(),
+            distribution_account: Address::generate(&env),
+            total_supply: 1_000_000,
+            tokens_sold: 99_000,
+            xlm_raised: 104_000_000,
+            launch_threshold_xlm: 100_000_000,
+            launch_threshold_percent: 80,
+            is_launched: true,
+            curve_params: linear_curve(),
+            creator: creator.clone(),
+            creation_time: 1234567890,
+        };
+
+        // Write records the way the schema v0 contract did, under bare keys
+        env.as_contract(&client.address, || {
+            let persistent = env.storage().persistent();
+            persistent.set(&launched_id, &legacy_token(&launched_id, true));
+            persistent.set(&open_id, &legacy_token(&open_id, false));
+            persistent.set(&token_address, &legacy_state);
+            persistent.set(&storage::legacy::TOKEN_COUNT, &2u32);
+            persistent.set(&(storage::legacy::CREATOR_TOKENS, creator.clone()), &vec![&env, token_address.clone()]);
+            persistent.set(&(storage::legacy::RATE_LIMIT, creator.clone()), &1234567890u64);
+        });
+
+        // v0 had no token list, so tokens are queued by id
+        assert_eq!(client.try_get_token_info(&launched_id), Err(Ok(ContractError::TokenNotFound)));
+        let unknown = String::from_str(&env, "NONE");
+        let token_ids = vec![&env, launched_id.clone(), open_id.clone(), unknown];
+        assert_eq!(client.queue_legacy_tokens(&token_ids), 2);
+        let result = client.try_create_token(
+            &creator,
+            &String::from_str(&env, "Blocked Token"),
+            &String::from_str(&env, "BLOCK"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &None,
+        );
+        assert_eq!(result, Err(Ok(ContractError::Paused)));
+
+        assert_eq!(client.try_migrate(&0), Err(Ok(ContractError::InvalidAmount)));
+        assert_eq!(client.migrate(&1), 1);
+        assert_eq!(client.migrate(&1), 0);
+        assert_eq!(client.migrate(&1), 0);
+        assert_eq!(client.queue_legacy_tokens(&vec![&env, launched_id.clone()]), 0);
+
+        // Every token is indexed and converted field by field
+        assert_eq!(client.get_token_count(), 2);
+        assert_eq!(client.get_token_id_at(&1), Some(open_id.clone()));
+        let info = client.get_token_info(&launched_id);
+        assert_eq!((info.name, info.symbol), (String::from_str(&env, "Old Token"), launched_id.clone()));
+        assert_eq!((info.total_supply, info.tokens_sold, info.current_price), (1_000_000, 99_000, 1_104));
+        assert_eq!((info.quote_asset, info.quote_raised), (xlm_token.clone(), 104_000_000));
+        assert_eq!((info.creator, info.creation_time), (creator.clone(), 1234567890));
+        assert!(info.is_launched && info.transition_executed);
+
+        // v0 held no reserve, so a token that had not launched migrates closed
+        assert!(client.get_token_info(&open_id).refunds_active);
+        let trader = funded_account(&env, &xlm_token);
+        let result = client.try_buy_tokens(&trader, &open_id, &1_000_000, &None);
+        assert_eq!(result, Err(Ok(ContractError::InvalidTokenState)));
+
+        env.as_contract(&client.address, || {
+            let state = storage::get_state(&env, &token_address).unwrap();
+            assert_eq!((state.quote_asset, state.quote_raised), (xlm_token.clone(), 104_000_000));
+            assert_eq!(state.launch_threshold, 100_000_000);
+            assert_eq!(storage::get_creator_tokens(&env, &creator), vec![&env, token_address.clone()]);
+            assert_eq!(storage::get_rate_limit_timestamp(&env, &creator), 1234567890);
+
+            // Nothing is left under the v0 keys
+            let persistent = env.storage().persistent();
+            assert!(!persistent.has(&launched_id));
+            assert!(!persistent.has(&token_address));
+            assert!(!persistent.has(&storage::legacy::TOKEN_COUNT));
+            assert!(!persistent.has(&(storage::legacy::CREATOR_TOKENS, creator.clone())));
+            assert!(!persistent.has(&(storage::legacy::RATE_LIMIT, creator.clone())));
+        });
+
+        // Trading resumes once the queue is empty
+        let token_id = client.create_token(
+            &creator,
+            &String::from_str(&env, "New Token"),
+            &String::from_str(&env, "NEW"),
+            &1_000_000i128,
+            &100_000_000i128,
+            &80u32,
+            &CurveSource::Custom(linear_curve()),
+            &None,
+            &None,
+        );
+        client.buy_tokens(&trader, &token_id, &1_000_000, &None);
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,5 @@
 use soroban_sdk::contracterror;
+use launchpad_types::CurveError;
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -13,6 +14,11 @@ pub enum ContractError {
     NotLaunched = 11,
     InvalidTokenState = 12,
     TokenNotFound = 13,
+    AlreadyInitialized = 14,
+    NotInitialized = 15,
+    PlatformShutdown = 16,
+    Paused = 17,
+    CreationLocked = 18,
     
     // Parameter validation
     InvalidAmount = 20,
@@ -20,6 +26,7 @@ pub enum ContractError {
     InvalidCurveParameters = 22,
     InvalidLaunchThreshold = 23,
     InvalidSupply = 24,
+    InsufficientSupply = 25,
     
     // Asset errors
     AssetNotFound = 30,
@@ -35,4 +42,77 @@ pub enum ContractError {
     // Rate limiting
     RateLimitExceeded = 50,
     MinimumNotMet = 51,
-}
\ No newline at end of file
+    MaximumExceeded = 52,
+
+    // Launch protection
+    WalletBuyCapExceeded = 60,
+    WalletHoldingCapExceeded = 61,
+    DevBuyLimitExceeded = 62,
+    InvalidLaunchProtection = 63,
+
+    // Circuit breaker
+    PriceImpactExceeded = 70,
+    LedgerMoveExceeded = 71,
+
+    // Presale
+    PresaleActive = 80,
+    PresaleEnded = 81,
+    InvalidProof = 82,
+    AllocationExceeded = 83,
+    InvalidPresaleConfig = 84,
+
+    // Opening auction
+    AuctionActive = 90,
+    AuctionEnded = 91,
+    AuctionNotEnded = 92,
+    AuctionNotSettled = 93,
+    NothingToClaim = 94,
+    InvalidAuctionConfig = 95,
+
+    // Commit-reveal buys
+    CommitmentNotFound = 100,
+    CommitmentExists = 101,
+    RevealTooEarly = 102,
+    RevealWindowClosed = 103,
+    RevealWindowOpen = 104,
+
+    // Referrals
+    SelfReferral = 110,
+    ReferralChain = 111,
+    ReferrerMismatch = 112,
+
+    // Creator vesting
+    InvalidVestingSchedule = 120,
+
+    // Claims
+    AlreadyClaimed = 130,
+    ClaimExpired = 131,
+    ClaimNotExpired = 132,
+
+    // Governance
+    ActionNotFound = 140,
+    ApprovalsMissing = 141,
+    TimelockActive = 142,
+
+    // Token metadata
+    InvalidMetadata = 150,
+
+    // Curve presets
+    PresetRegistryFull = 160,
+
+    // Limit orders
+    OrderNotFound = 170,
+    OrderExpired = 171,
+    TriggerNotReached = 172,
+}
+
+impl From<CurveError> for ContractError {
+    fn from(error: CurveError) -> Self {
+        match error {
+            CurveError::InvalidAmount => ContractError::InvalidAmount,
+            CurveError::InvalidCurveParameters => ContractError::InvalidCurveParameters,
+            CurveError::InvalidSupply => ContractError::InvalidSupply,
+            CurveError::CalculationOverflow => ContractError::CalculationOverflow,
+        }
+    }
+}
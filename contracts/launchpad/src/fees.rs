@@ -0,0 +1,86 @@
+use crate::errors::ContractError;
+
+const MAX_FEE_BPS: u32 = 1000; // 10% in basis points
+
+pub struct PlatformFee;
+
+impl PlatformFee {
+    /// Validate a platform fee rate
+    pub fn validate_fee_bps(fee_bps: u32) -> Result<(), ContractError> {
+        if fee_bps > MAX_FEE_BPS {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        Ok(())
+    }
+
+    /// Calculate the fee charged on an XLM amount
+    pub fn calculate_fee(xlm_amount: i128, fee_bps: u32) -> Result<i128, ContractError> {
+        if xlm_amount < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        xlm_amount
+            .checked_mul(fee_bps as i128)
+            .map(|scaled| scaled / 10000)
+            .ok_or(ContractError::CalculationOverflow)
+    }
+
+    /// Smallest amount to pay in so that `net` is left once the fee is taken out of it
+    pub fn gross_up(net: i128, fee_bps: u32) -> Result<i128, ContractError> {
+        if net < 0 || fee_bps >= 10000 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let kept_bps = 10000 - fee_bps as i128;
+        let mut gross = net
+            .checked_mul(10000)
+            .map(|scaled| (scaled + kept_bps - 1) / kept_bps)
+            .ok_or(ContractError::CalculationOverflow)?;
+
+        // Fees round down, so a slightly smaller amount may still leave `net`
+        while gross > net && gross - 1 - Self::calculate_fee(gross - 1, fee_bps)? >= net {
+            gross -= 1;
+        }
+
+        Ok(gross)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_fee() {
+        assert_eq!(PlatformFee::calculate_fee(10_000_000, 100).unwrap(), 100_000);
+        assert_eq!(PlatformFee::calculate_fee(10_000_000, 0).unwrap(), 0);
+
+        // Fees round down in the buyer's favour
+        assert_eq!(PlatformFee::calculate_fee(99, 100).unwrap(), 0);
+
+        assert_eq!(PlatformFee::calculate_fee(-1, 100), Err(ContractError::InvalidAmount));
+    }
+
+    #[test]
+    fn test_gross_up() {
+        // The fee on the grossed-up amount leaves exactly the net
+        assert_eq!(PlatformFee::gross_up(10_450_000, 100).unwrap(), 10_555_555);
+        assert_eq!(PlatformFee::calculate_fee(10_555_555, 100).unwrap(), 105_555);
+        assert_eq!(PlatformFee::gross_up(10_450_000, 0).unwrap(), 10_450_000);
+
+        for net in [0, 1, 99, 12_345, 1_000_001] {
+            let gross = PlatformFee::gross_up(net, 250).unwrap();
+            assert!(gross - PlatformFee::calculate_fee(gross, 250).unwrap() >= net);
+            assert!(gross == net || gross - 1 - PlatformFee::calculate_fee(gross - 1, 250).unwrap() < net);
+        }
+
+        assert_eq!(PlatformFee::gross_up(-1, 100), Err(ContractError::InvalidAmount));
+    }
+
+    #[test]
+    fn test_validate_fee_bps() {
+        assert!(PlatformFee::validate_fee_bps(1000).is_ok());
+        assert_eq!(PlatformFee::validate_fee_bps(1001), Err(ContractError::InvalidAmount));
+    }
+}
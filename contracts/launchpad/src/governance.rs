@@ -0,0 +1,130 @@
+use soroban_sdk::{Address, Vec};
+use crate::{
+    errors::ContractError,
+    types::{Governance, QueuedAction},
+};
+
+const MAX_ADMINS: u32 = 10;
+const MIN_DELAY_LEDGERS: u32 = 720;        // Approximately 1 hour, long enough to react to a proposal
+const MAX_DELAY_LEDGERS: u32 = 120_960;    // Approximately 7 days, so admin actions can't be frozen
+
+pub struct AdminGovernance;
+
+impl AdminGovernance {
+    /// Validate an M-of-N admin set and its timelock
+    pub fn validate_governance(governance: &Governance) -> Result<(), ContractError> {
+        let admin_count = governance.admins.len();
+        if admin_count == 0 || admin_count > MAX_ADMINS {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        if governance.threshold == 0 || governance.threshold > admin_count {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        if governance.delay_ledgers < MIN_DELAY_LEDGERS || governance.delay_ledgers > MAX_DELAY_LEDGERS {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        for (i, admin) in governance.admins.iter().enumerate() {
+            if governance.admins.iter().skip(i + 1).any(|other| other == admin) {
+                return Err(ContractError::InvalidAmount);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check whether an address belongs to the admin set
+    pub fn is_admin(governance: &Governance, account: &Address) -> bool {
+        governance.admins.contains(account)
+    }
+
+    /// Record an admin's approval, ignoring repeats
+    pub fn add_approval(approvals: &mut Vec<Address>, admin: &Address) {
+        if !approvals.contains(admin) {
+            approvals.push_back(admin.clone());
+        }
+    }
+
+    /// Check that a queued action has enough approvals and its timelock has passed
+    pub fn check_execute(
+        governance: &Governance,
+        queued: &QueuedAction,
+        current_ledger: u32,
+    ) -> Result<(), ContractError> {
+        // Approvals from admins removed since the proposal no longer count
+        let approvals = queued.approvals.iter()
+            .filter(|approver| Self::is_admin(governance, approver))
+            .count() as u32;
+        if approvals < governance.threshold {
+            return Err(ContractError::ApprovalsMissing);
+        }
+
+        if current_ledger < queued.eta_ledger {
+            return Err(ContractError::TimelockActive);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AdminAction;
+    use soroban_sdk::{testutils::Address as _, vec, Env};
+
+    fn create_governance(env: &Env) -> Governance {
+        Governance {
+            admins: vec![env, Address::generate(env), Address::generate(env), Address::generate(env)],
+            threshold: 2,
+            delay_ledgers: 720,
+        }
+    }
+
+    #[test]
+    fn test_validate_governance() {
+        let env = Env::default();
+        let mut governance = create_governance(&env);
+        assert!(AdminGovernance::validate_governance(&governance).is_ok());
+
+        governance.threshold = 4;
+        assert_eq!(AdminGovernance::validate_governance(&governance), Err(ContractError::InvalidAmount));
+
+        let mut governance = create_governance(&env);
+        governance.admins.push_back(governance.admins.get(0).unwrap());
+        assert_eq!(AdminGovernance::validate_governance(&governance), Err(ContractError::InvalidAmount));
+
+        // The timelock can be neither switched off nor set long enough to freeze admin actions
+        let mut governance = create_governance(&env);
+        governance.delay_ledgers = MIN_DELAY_LEDGERS - 1;
+        assert_eq!(AdminGovernance::validate_governance(&governance), Err(ContractError::InvalidAmount));
+        governance.delay_ledgers = MAX_DELAY_LEDGERS + 1;
+        assert_eq!(AdminGovernance::validate_governance(&governance), Err(ContractError::InvalidAmount));
+        governance.delay_ledgers = MAX_DELAY_LEDGERS;
+        assert!(AdminGovernance::validate_governance(&governance).is_ok());
+    }
+
+    #[test]
+    fn test_check_execute() {
+        let env = Env::default();
+        let governance = create_governance(&env);
+        let mut queued = QueuedAction {
+            action: AdminAction::Unpause,
+            approvals: vec![&env, governance.admins.get(0).unwrap()],
+            eta_ledger: 200,
+        };
+
+        assert_eq!(AdminGovernance::check_execute(&governance, &queued, 200), Err(ContractError::ApprovalsMissing));
+
+        // Repeat approvals and outsiders don't count towards the threshold
+        AdminGovernance::add_approval(&mut queued.approvals, &governance.admins.get(0).unwrap());
+        queued.approvals.push_back(Address::generate(&env));
+        assert_eq!(AdminGovernance::check_execute(&governance, &queued, 200), Err(ContractError::ApprovalsMissing));
+
+        AdminGovernance::add_approval(&mut queued.approvals, &governance.admins.get(1).unwrap());
+        assert_eq!(AdminGovernance::check_execute(&governance, &queued, 199), Err(ContractError::TimelockActive));
+        assert!(AdminGovernance::check_execute(&governance, &queued, 200).is_ok());
+    }
+}
@@ -0,0 +1,105 @@
+use soroban_sdk::{contractclient, Address, Env, String};
+use crate::{
+    storage,
+    types::{HookNotice, LaunchHook, TokenInfo},
+};
+
+/// Interface a creator's hook contract implements to follow a launch
+#[allow(dead_code)]
+#[contractclient(name = "LaunchHookClient")]
+pub trait LaunchHookInterface {
+    /// Called once when the token graduates
+    fn on_graduate(env: Env, token_id: String, quote_raised: i128, tokens_sold: i128, price: i128);
+
+    /// Called after each curve trade when the hook subscribed to trades
+    fn on_trade(
+        env: Env,
+        token_id: String,
+        trader: Address,
+        is_buy: bool,
+        token_amount: i128,
+        quote_amount: i128,
+        price: i128,
+    );
+}
+
+pub struct LaunchHooks;
+
+impl LaunchHooks {
+    /// Queue a graduation notice for a token's hook, if any
+    ///
+    /// Hooks never run inside trades or the launch transition: notices are
+    /// delivered one per transaction by `deliver_next`, so a hook that panics or
+    /// exhausts the budget can only fail its own delivery.
+    pub fn queue_graduation(env: &Env, token_id: &String, token_info: &TokenInfo) {
+        if storage::get_launch_hook(env, token_id).is_none() {
+            return;
+        }
+
+        storage::push_hook_notice(env, token_id, &HookNotice::Graduate(
+            token_info.quote_raised,
+            token_info.tokens_sold,
+            token_info.current_price,
+        ));
+    }
+
+    /// Queue a trade notice for a token's hook if it subscribed to trades
+    pub fn queue_trade(
+        env: &Env,
+        token_id: &String,
+        trader: &Address,
+        is_buy: bool,
+        token_amount: i128,
+        quote_amount: i128,
+        price: i128,
+    ) {
+        if !storage::get_launch_hook(env, token_id).is_some_and(|hook| hook.on_trade) {
+            return;
+        }
+
+        storage::push_hook_notice(env, token_id, &HookNotice::Trade(
+            trader.clone(),
+            is_buy,
+            token_amount,
+            quote_amount,
+            price,
+        ));
+    }
+
+    /// Deliver a token's oldest queued notice, returning false once the queue is empty
+    ///
+    /// Errors and panics in the hook are caught and reported with a `hookfail`
+    /// event. A notice whose delivery exhausts the budget stays queued until the
+    /// admin detaches the hook, after which queued notices are dropped undelivered.
+    pub fn deliver_next(env: &Env, token_id: &String) -> bool {
+        let Some(notice) = storage::pop_hook_notice(env, token_id) else {
+            return false;
+        };
+        let Some(hook) = storage::get_launch_hook(env, token_id) else {
+            return true;
+        };
+
+        let client = LaunchHookClient::new(env, &hook.contract);
+        let delivered = match notice {
+            HookNotice::Graduate(quote_raised, tokens_sold, price) => {
+                matches!(client.try_on_graduate(token_id, &quote_raised, &tokens_sold, &price), Ok(Ok(())))
+            }
+            HookNotice::Trade(trader, is_buy, token_amount, quote_amount, price) => matches!(
+                client.try_on_trade(token_id, &trader, &is_buy, &token_amount, &quote_amount, &price),
+                Ok(Ok(()))
+            ),
+        };
+
+        if !delivered {
+            Self::report_failure(env, &hook, token_id);
+        }
+        true
+    }
+
+    fn report_failure(env: &Env, hook: &LaunchHook, token_id: &String) {
+        env.events().publish(
+            (soroban_sdk::symbol_short!("hookfail"),),
+            (token_id.clone(), hook.contract.clone())
+        );
+    }
+}
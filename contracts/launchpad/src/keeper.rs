@@ -0,0 +1,46 @@
+use crate::errors::ContractError;
+
+const MAX_KEEPER_BOUNTY_BPS: u32 = 100; // 1% of a token's reserve
+
+pub struct KeeperBounty;
+
+impl KeeperBounty {
+    /// Validate the keeper bounty rate
+    pub fn validate_bounty_bps(bounty_bps: u32) -> Result<(), ContractError> {
+        if bounty_bps > MAX_KEEPER_BOUNTY_BPS {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        Ok(())
+    }
+
+    /// Calculate the bounty paid out of a token's reserve for a maintenance call
+    pub fn calculate_bounty(reserve: i128, bounty_bps: u32) -> Result<i128, ContractError> {
+        if reserve <= 0 {
+            return Ok(0);
+        }
+
+        reserve
+            .checked_mul(bounty_bps as i128)
+            .map(|scaled| scaled / 10000)
+            .ok_or(ContractError::CalculationOverflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_bounty() {
+        assert_eq!(KeeperBounty::calculate_bounty(100_000_000, 10).unwrap(), 100_000);
+        assert_eq!(KeeperBounty::calculate_bounty(100_000_000, 0).unwrap(), 0);
+        assert_eq!(KeeperBounty::calculate_bounty(0, 100).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_validate_bounty_bps() {
+        assert!(KeeperBounty::validate_bounty_bps(100).is_ok());
+        assert_eq!(KeeperBounty::validate_bounty_bps(101), Err(ContractError::InvalidAmount));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{
+    errors::ContractError,
+    types::LaunchProtection,
+};
+
+pub struct LaunchGuard;
+
+impl LaunchGuard {
+    /// Validate protection settings against the token supply
+    pub fn validate_protection(
+        protection: &LaunchProtection,
+        total_supply: i128,
+    ) -> Result<(), ContractError> {
+        if let Some(max_buy) = protection.max_buy_per_wallet {
+            if max_buy <= 0 || protection.window_ledgers == 0 {
+                return Err(ContractError::InvalidLaunchProtection);
+            }
+        }
+
+        if let Some(max_percent) = protection.max_wallet_percent {
+            if max_percent == 0 || max_percent > 100 {
+                return Err(ContractError::InvalidLaunchProtection);
+            }
+        }
+
+        if let Some(max_dev_buy) = protection.max_dev_buy {
+            if max_dev_buy < 0 || max_dev_buy > total_supply {
+                return Err(ContractError::InvalidLaunchProtection);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check a purchase against the token's launch protection
+    ///
+    /// `purchased` is the wallet's cumulative pre-launch buys and `holding`
+    /// its current balance, both before this purchase.
+    pub fn check_purchase(
+        protection: &LaunchProtection,
+        is_creator: bool,
+        ledgers_since_creation: u32,
+        purchased: i128,
+        holding: i128,
+        tokens_to_receive: i128,
+        total_supply: i128,
+    ) -> Result<(), ContractError> {
+        if is_creator {
+            if let Some(max_dev_buy) = protection.max_dev_buy {
+                if purchased + tokens_to_receive > max_dev_buy {
+                    return Err(ContractError::DevBuyLimitExceeded);
+                }
+            }
+        }
+
+        if ledgers_since_creation < protection.window_ledgers {
+            if let Some(max_buy) = protection.max_buy_per_wallet {
+                if purchased + tokens_to_receive > max_buy {
+                    return Err(ContractError::WalletBuyCapExceeded);
+                }
+            }
+        }
+
+        if let Some(room) = Self::holding_room(protection, holding, total_supply) {
+            if tokens_to_receive > room {
+                return Err(ContractError::WalletHoldingCapExceeded);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Tokens a wallet holding `holding` may still receive under the holding cap, if one is set
+    pub fn holding_room(
+        protection: &LaunchProtection,
+        holding: i128,
+        total_supply: i128,
+    ) -> Option<i128> {
+        protection.max_wallet_percent.map(|max_percent| {
+            let max_holding = (total_supply * max_percent as i128) / 100;
+            (max_holding - holding).max(0)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_protection() -> LaunchProtection {
+        LaunchProtection {
+            window_ledgers: 10,
+            max_buy_per_wallet: Some(1_000),
+            max_wallet_percent: Some(5),
+            max_dev_buy: Some(500),
+        }
+    }
+
+    #[test]
+    fn test_validate_protection() {
+        let total_supply = 1_000_000i128;
+
+        // Test valid settings
+        assert!(LaunchGuard::validate_protection(&create_protection(), total_supply).is_ok());
+
+        // Test wallet cap without a window
+        let mut protection = create_protection();
+        protection.window_ledgers = 0;
+        assert_eq!(
+            LaunchGuard::validate_protection(&protection, total_supply),
+            Err(ContractError::InvalidLaunchProtection)
+        );
+
+        // Test holding cap above 100%
+        let mut protection = create_protection();
+        protection.max_wallet_percent = Some(101);
+        assert_eq!(
+            LaunchGuard::validate_protection(&protection, total_supply),
+            Err(ContractError::InvalidLaunchProtection)
+        );
+
+        // Test dev buy above supply
+        let mut protection = create_protection();
+        protection.max_dev_buy = Some(total_supply + 1);
+        assert_eq!(
+            LaunchGuard::validate_protection(&protection, total_supply),
+            Err(ContractError::InvalidLaunchProtection)
+        );
+    }
+
+    #[test]
+    fn test_wallet_buy_cap_window() {
+        let protection = create_protection();
+        let total_supply = 1_000_000i128;
+
+        // Inside the window the per-wallet cap applies
+        assert!(LaunchGuard::check_purchase(&protection, false, 0, 0, 0, 1_000, total_supply).is_ok());
+        assert_eq!(
+            LaunchGuard::check_purchase(&protection, false, 9, 600, 600, 500, total_supply),
+            Err(ContractError::WalletBuyCapExceeded)
+        );
+
+        // After the window only the holding cap applies
+        assert!(LaunchGuard::check_purchase(&protection, false, 10, 600, 600, 500, total_supply).is_ok());
+    }
+
+    #[test]
+    fn test_wallet_holding_cap() {
+        let protection = create_protection();
+        let total_supply = 1_000_000i128;
+
+        // 5% of 1_000_000 = 50_000 tokens
+        assert!(LaunchGuard::check_purchase(&protection, false, 20, 0, 49_000, 1_000, total_supply).is_ok());
+        assert_eq!(
+            LaunchGuard::check_purchase(&protection, false, 20, 0, 49_000, 1_001, total_supply),
+            Err(ContractError::WalletHoldingCapExceeded)
+        );
+
+        assert_eq!(LaunchGuard::holding_room(&protection, 49_000, total_supply), Some(1_000));
+        assert_eq!(LaunchGuard::holding_room(&protection, 60_000, total_supply), Some(0));
+        assert_eq!(LaunchGuard::holding_room(&LaunchProtection::none(), 60_000, total_supply), None);
+    }
+
+    #[test]
+    fn test_dev_buy_limit() {
+        let protection = create_protection();
+        let total_supply = 1_000_000i128;
+
+        // The creator hits the dev buy limit before the wallet cap
+        assert!(LaunchGuard::check_purchase(&protection, true, 0, 0, 0, 500, total_supply).is_ok());
+        assert_eq!(
+            LaunchGuard::check_purchase(&protection, true, 0, 0, 0, 501, total_supply),
+            Err(ContractError::DevBuyLimitExceeded)
+        );
+
+        // Other wallets are not subject to the dev buy limit
+        assert!(LaunchGuard::check_purchase(&protection, false, 0, 0, 0, 501, total_supply).is_ok());
+    }
+}
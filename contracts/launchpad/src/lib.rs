@@ -2,9 +2,31 @@
 
 mod contract;
 mod storage;
-mod types;
+pub mod types;
 mod errors;
-mod bonding_curve;
 mod asset_manager;
+mod launch_protection;
+mod circuit_breaker;
+mod merkle;
+mod auction;
+mod commit_reveal;
+mod fees;
+mod referral;
+mod vesting;
+mod airdrop;
+mod keeper;
+mod refund;
+mod governance;
+mod oracle;
+mod hooks;
+mod migration;
+mod metadata;
+mod presets;
+mod orders;
+mod sell_fee;
 
-pub use contract::LaunchpadContract;
\ No newline at end of file
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+pub use contract::{LaunchpadContract, LaunchpadContractClient};
+pub use errors::ContractError;
\ No newline at end of file
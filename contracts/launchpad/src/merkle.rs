@@ -0,0 +1,71 @@
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+pub struct MerkleProof;
+
+impl MerkleProof {
+    /// Hash an (address, amount) allowlist entry into a leaf
+    pub fn leaf_hash(env: &Env, account: &Address, amount: i128) -> BytesN<32> {
+        let mut data: Bytes = account.clone().to_xdr(env);
+        data.extend_from_array(&amount.to_be_bytes());
+        env.crypto().sha256(&data).to_bytes()
+    }
+
+    /// Hash an (index, address, amount) distribution entry into a leaf
+    pub fn indexed_leaf_hash(env: &Env, index: u32, account: &Address, amount: i128) -> BytesN<32> {
+        let mut data = Bytes::from_array(env, &index.to_be_bytes());
+        data.append(&account.clone().to_xdr(env));
+        data.extend_from_array(&amount.to_be_bytes());
+        env.crypto().sha256(&data).to_bytes()
+    }
+
+    /// Hash two nodes into their parent, ordering the pair so proofs need no path bits
+    pub fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        let mut data: Bytes = left.clone().into();
+        data.append(&right.clone().into());
+        env.crypto().sha256(&data).to_bytes()
+    }
+
+    /// Verify that a leaf belongs to the tree with the given root
+    pub fn verify(
+        env: &Env,
+        root: &BytesN<32>,
+        leaf: &BytesN<32>,
+        proof: &Vec<BytesN<32>>,
+    ) -> bool {
+        let mut computed = leaf.clone();
+        for node in proof.iter() {
+            computed = Self::hash_pair(env, &computed, &node);
+        }
+
+        computed == *root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, vec};
+
+    #[test]
+    fn test_verify_proof() {
+        let env = Env::default();
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+
+        let leaf_a = MerkleProof::leaf_hash(&env, &alice, 1_000);
+        let leaf_b = MerkleProof::leaf_hash(&env, &bob, 2_000);
+        let leaf_c = MerkleProof::leaf_hash(&env, &carol, 3_000);
+        let node_ab = MerkleProof::hash_pair(&env, &leaf_a, &leaf_b);
+        let root = MerkleProof::hash_pair(&env, &node_ab, &leaf_c);
+
+        // Test valid proofs
+        assert!(MerkleProof::verify(&env, &root, &leaf_a, &vec![&env, leaf_b.clone(), leaf_c.clone()]));
+        assert!(MerkleProof::verify(&env, &root, &leaf_c, &vec![&env, node_ab]));
+
+        // Test wrong amount
+        let forged = MerkleProof::leaf_hash(&env, &alice, 5_000);
+        assert!(!MerkleProof::verify(&env, &root, &forged, &vec![&env, leaf_b, leaf_c]));
+    }
+}
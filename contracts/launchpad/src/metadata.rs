@@ -0,0 +1,92 @@
+use crate::{
+    errors::ContractError,
+    types::{TokenInfo, TokenMetadata},
+};
+
+const MAX_URI_LEN: u32 = 256; // Image, website and social links
+const MAX_DESCRIPTION_LEN: u32 = 1024;
+const MAX_SOCIALS: u32 = 8;
+
+pub struct Metadata;
+
+impl Metadata {
+    /// Validate metadata field sizes so a record stays cheap to store and index
+    pub fn validate(metadata: &TokenMetadata) -> Result<(), ContractError> {
+        if metadata.image_uri.len() > MAX_URI_LEN
+            || metadata.website.len() > MAX_URI_LEN
+            || metadata.description.len() > MAX_DESCRIPTION_LEN
+        {
+            return Err(ContractError::InvalidMetadata);
+        }
+
+        if metadata.socials.len() > MAX_SOCIALS {
+            return Err(ContractError::InvalidMetadata);
+        }
+
+        for link in metadata.socials.iter() {
+            if link.is_empty() || link.len() > MAX_URI_LEN {
+                return Err(ContractError::InvalidMetadata);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that a token's metadata may still change; it freezes at graduation
+    pub fn check_editable(token_info: &TokenInfo) -> Result<(), ContractError> {
+        if token_info.is_launched {
+            return Err(ContractError::AlreadyLaunched);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{BytesN, Env, String, Vec};
+
+    fn create_metadata(env: &Env) -> TokenMetadata {
+        TokenMetadata {
+            image_uri: String::from_str(env, "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"),
+            description: String::from_str(env, "A token for testing"),
+            website: String::from_str(env, "https://example.com"),
+            socials: Vec::from_array(env, [String::from_str(env, "https://x.com/example")]),
+            content_hash: BytesN::from_array(env, &[1; 32]),
+        }
+    }
+
+    #[test]
+    fn test_validate_metadata() {
+        let env = Env::default();
+        assert!(Metadata::validate(&create_metadata(&env)).is_ok());
+
+        // Empty fields are allowed
+        let mut metadata = create_metadata(&env);
+        metadata.website = String::from_str(&env, "");
+        metadata.socials = Vec::new(&env);
+        assert!(Metadata::validate(&metadata).is_ok());
+
+        // Oversized description
+        let mut metadata = create_metadata(&env);
+        metadata.description = String::from_bytes(&env, &[b'a'; 1025]);
+        assert_eq!(Metadata::validate(&metadata), Err(ContractError::InvalidMetadata));
+
+        // Oversized image link
+        let mut metadata = create_metadata(&env);
+        metadata.image_uri = String::from_bytes(&env, &[b'a'; 257]);
+        assert_eq!(Metadata::validate(&metadata), Err(ContractError::InvalidMetadata));
+
+        // Too many or empty social links
+        let mut metadata = create_metadata(&env);
+        for _ in 0..8 {
+            metadata.socials.push_back(String::from_str(&env, "https://x.com/example"));
+        }
+        assert_eq!(Metadata::validate(&metadata), Err(ContractError::InvalidMetadata));
+
+        let mut metadata = create_metadata(&env);
+        metadata.socials.push_back(String::from_str(&env, ""));
+        assert_eq!(Metadata::validate(&metadata), Err(ContractError::InvalidMetadata));
+    }
+}
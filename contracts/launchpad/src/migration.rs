@@ -0,0 +1,181 @@
+use soroban_sdk::{Address, Env, String, Vec};
+use crate::{
+    storage::{self, legacy},
+    types::{CreatorAllocation, CurveParameters, CurveType, LaunchProtection, LaunchpadState, TokenInfo},
+};
+
+const LEGACY_LAUNCH_THRESHOLD: i128 = 100_000_000; // Schema v0 graduated every token at 10 XLM raised
+
+pub struct StorageMigration;
+
+impl StorageMigration {
+    /// Check whether schema v0 tokens are still queued for migration
+    pub fn is_pending(env: &Env) -> bool {
+        let (head, tail) = storage::get_migration_queue(env);
+        head < tail
+    }
+
+    /// Queue a schema v0 token for migration
+    ///
+    /// Returns false if there is no such token or its id is already taken by a
+    /// current token.
+    pub fn queue_token(env: &Env, token_id: &String) -> bool {
+        if storage::has_token(env, token_id) || Self::get_legacy_token(env, token_id).is_none() {
+            return false;
+        }
+
+        storage::push_legacy_token(env, token_id);
+        true
+    }
+
+    /// Convert a schema v0 token, index it and move its creator's records
+    ///
+    /// Returns false if the token was already migrated.
+    pub fn migrate_token(env: &Env, token_id: &String, quote_asset: &Address) -> bool {
+        if storage::has_token(env, token_id) {
+            return false;
+        }
+        let Some(token) = Self::get_legacy_token(env, token_id) else {
+            return false;
+        };
+
+        let creator = token.creator.clone();
+        storage::set_token(env, token_id, &Self::convert_token(token, quote_asset));
+        storage::add_token(env, token_id);
+        env.storage().persistent().remove(token_id);
+
+        Self::migrate_creator(env, &creator, quote_asset);
+        true
+    }
+
+    /// Drop the schema v0 keys left once every queued token is migrated
+    pub fn finish(env: &Env) {
+        env.storage().persistent().remove(&legacy::TOKEN_COUNT);
+    }
+
+    /// Convert a schema v0 token record field by field
+    ///
+    /// v0 priced buys off a stepped spot price without taking the XLM or
+    /// recording balances, so there is no reserve or holder ledger to resume
+    /// trading against. Launched tokens keep their graduation; the rest migrate
+    /// with refunds open, which closes them.
+    pub fn convert_token(token: legacy::TokenInfo, quote_asset: &Address) -> TokenInfo {
+        TokenInfo {
+            name: token.name,
+            symbol: token.symbol,
+            total_supply: token.total_supply,
+            tokens_sold: token.tokens_sold,
+            quote_asset: quote_asset.clone(),
+            quote_raised: token.xlm_raised,
+            launch_threshold: LEGACY_LAUNCH_THRESHOLD,
+            launch_threshold_percent: 0,
+            current_price: token.current_price,
+            launch_progress_percent: token.launch_progress_percent,
+            is_launched: token.is_launched,
+            launch_ledger: 0,
+            transition_executed: token.is_launched,
+            launch_deadline: 0,
+            refunds_active: !token.is_launched,
+            tokens_refunded: 0,
+            creator: token.creator,
+            creation_time: token.creation_time,
+            creation_ledger: 0,
+            curve_params: CurveParameters {
+                curve_type: CurveType::Linear,
+                base_price: token.current_price,
+                price_multiplier: 0,
+            },
+            protection: LaunchProtection::none(),
+            creator_allocation: CreatorAllocation::none(),
+            creator_reserved: 0,
+            creator_vested: 0,
+            creator_claimed: 0,
+        }
+    }
+
+    /// Convert a schema v0 launchpad state record field by field
+    pub fn convert_state(state: legacy::LaunchpadState, quote_asset: &Address) -> LaunchpadState {
+        LaunchpadState {
+            token_address: state.token_address,
+            distribution_account: state.distribution_account,
+            total_supply: state.total_supply,
+            tokens_sold: state.tokens_sold,
+            quote_asset: quote_asset.clone(),
+            quote_raised: state.xlm_raised,
+            launch_threshold: state.launch_threshold_xlm,
+            launch_threshold_percent: state.launch_threshold_percent,
+            is_launched: state.is_launched,
+            curve_params: state.curve_params,
+            creator: state.creator,
+            creation_time: state.creation_time,
+        }
+    }
+
+    fn get_legacy_token(env: &Env, token_id: &String) -> Option<legacy::TokenInfo> {
+        env.storage().persistent().get(token_id)
+    }
+
+    /// Move a creator's token list, the state of each listed token and the rate limit
+    fn migrate_creator(env: &Env, creator: &Address, quote_asset: &Address) {
+        let persistent = env.storage().persistent();
+
+        let tokens_key = (legacy::CREATOR_TOKENS, creator.clone());
+        if let Some(tokens) = persistent.get::<_, Vec<Address>>(&tokens_key) {
+            for token_address in tokens.iter() {
+                if let Some(state) = persistent.get::<_, legacy::LaunchpadState>(&token_address) {
+                    storage::set_state(env, &token_address, &Self::convert_state(state, quote_asset));
+                    persistent.remove(&token_address);
+                }
+            }
+
+            storage::set_creator_tokens(env, creator, &tokens);
+            persistent.remove(&tokens_key);
+        }
+
+        let rate_key = (legacy::RATE_LIMIT, creator.clone());
+        if let Some(timestamp) = persistent.get::<_, u64>(&rate_key) {
+            storage::set_rate_limit_timestamp(env, creator, timestamp);
+            persistent.remove(&rate_key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    fn legacy_token(env: &Env, is_launched: bool) -> legacy::TokenInfo {
+        legacy::TokenInfo {
+            name: String::from_str(env, "Old Token"),
+            symbol: String::from_str(env, "OLD"),
+            total_supply: 1_000_000,
+            tokens_sold: 99_000,
+            xlm_raised: 104_000_000,
+            current_price: 1_104,
+            launch_progress_percent: 9,
+            is_launched,
+            creator: Address::generate(env),
+            creation_time: 1234567890,
+        }
+    }
+
+    #[test]
+    fn test_convert_token() {
+        let env = Env::default();
+        let quote_asset = Address::generate(&env);
+
+        let legacy = legacy_token(&env, true);
+        let token = StorageMigration::convert_token(legacy.clone(), &quote_asset);
+        assert_eq!((token.name, token.symbol), (legacy.name, legacy.symbol));
+        assert_eq!((token.total_supply, token.tokens_sold), (1_000_000, 99_000));
+        assert_eq!((token.quote_asset, token.quote_raised), (quote_asset.clone(), 104_000_000));
+        assert_eq!((token.current_price, token.launch_progress_percent), (1_104, 9));
+        assert_eq!((token.creator, token.creation_time), (legacy.creator, 1234567890));
+        assert!(token.is_launched && token.transition_executed && !token.refunds_active);
+
+        // Tokens that had not launched migrate closed
+        let token = StorageMigration::convert_token(legacy_token(&env, false), &quote_asset);
+        assert!(!token.is_launched && !token.transition_executed && token.refunds_active);
+    }
+}
@@ -0,0 +1,130 @@
+use soroban_sdk::{Env, Vec};
+use crate::{
+    errors::ContractError,
+    types::{Observation, OracleState},
+};
+
+const MAX_OBSERVATIONS: u32 = 32; // Ring buffer size per token
+
+pub struct PriceOracle;
+
+impl PriceOracle {
+    /// Start a token's accumulator at its opening price
+    pub fn initialize(env: &Env, timestamp: u64, price: i128) -> OracleState {
+        OracleState {
+            observations: Vec::from_array(env, [Observation {
+                timestamp,
+                cumulative_price: 0,
+                price,
+            }]),
+            newest: 0,
+        }
+    }
+
+    /// Accumulate the price in effect since the last trade and record the new price
+    ///
+    /// Trades within the same ledger timestamp only replace the newest price, so
+    /// a single ledger cannot add weight to the average.
+    pub fn record(state: &mut OracleState, timestamp: u64, price: i128) -> Result<(), ContractError> {
+        let mut newest = state.observations.get(state.newest).ok_or(ContractError::NotInitialized)?;
+
+        if timestamp <= newest.timestamp {
+            newest.price = price;
+            state.observations.set(state.newest, newest);
+            return Ok(());
+        }
+
+        let observation = Observation {
+            timestamp,
+            cumulative_price: Self::cumulative_at(&newest, timestamp)?,
+            price,
+        };
+
+        if state.observations.len() < MAX_OBSERVATIONS {
+            state.observations.push_back(observation);
+            state.newest = state.observations.len() - 1;
+        } else {
+            state.newest = (state.newest + 1) % MAX_OBSERVATIONS;
+            state.observations.set(state.newest, observation);
+        }
+
+        Ok(())
+    }
+
+    /// Time-weighted average price over the `window_seconds` ending at `now`
+    pub fn twap(state: &OracleState, now: u64, window_seconds: u64) -> Result<i128, ContractError> {
+        if window_seconds == 0 || window_seconds > now {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let newest = state.observations.get(state.newest).ok_or(ContractError::NotInitialized)?;
+        let target = now - window_seconds;
+
+        // The window must start inside the recorded history
+        let start = state.observations.iter()
+            .filter(|observation| observation.timestamp <= target)
+            .max_by_key(|observation| observation.timestamp)
+            .ok_or(ContractError::InvalidAmount)?;
+
+        let cumulative_now = Self::cumulative_at(&newest, now.max(newest.timestamp))?;
+        let cumulative_start = Self::cumulative_at(&start, target)?;
+
+        Ok((cumulative_now - cumulative_start) / window_seconds as i128)
+    }
+
+    fn cumulative_at(observation: &Observation, timestamp: u64) -> Result<i128, ContractError> {
+        let elapsed = (timestamp - observation.timestamp) as i128;
+
+        observation.price
+            .checked_mul(elapsed)
+            .and_then(|weighted| weighted.checked_add(observation.cumulative_price))
+            .ok_or(ContractError::CalculationOverflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_weighted_average() {
+        let env = Env::default();
+        let mut state = PriceOracle::initialize(&env, 1_000, 100);
+
+        // 100 for 60s, then 200 for 40s
+        PriceOracle::record(&mut state, 1_060, 200).unwrap();
+        assert_eq!(PriceOracle::twap(&state, 1_100, 100).unwrap(), 140);
+        assert_eq!(PriceOracle::twap(&state, 1_100, 40).unwrap(), 200);
+
+        // Windows reaching before the first observation are rejected
+        assert_eq!(PriceOracle::twap(&state, 1_100, 101), Err(ContractError::InvalidAmount));
+        assert_eq!(PriceOracle::twap(&state, 1_100, 0), Err(ContractError::InvalidAmount));
+    }
+
+    #[test]
+    fn test_same_timestamp_replaces_price() {
+        let env = Env::default();
+        let mut state = PriceOracle::initialize(&env, 1_000, 100);
+
+        PriceOracle::record(&mut state, 1_050, 10_000).unwrap();
+        PriceOracle::record(&mut state, 1_050, 100).unwrap();
+        assert_eq!(state.observations.len(), 2);
+        assert_eq!(PriceOracle::twap(&state, 1_100, 100).unwrap(), 100);
+    }
+
+    #[test]
+    fn test_ring_buffer_is_bounded() {
+        let env = Env::default();
+        let mut state = PriceOracle::initialize(&env, 0, 100);
+
+        for i in 1..=40u64 {
+            PriceOracle::record(&mut state, i * 10, 100).unwrap();
+        }
+
+        assert_eq!(state.observations.len(), MAX_OBSERVATIONS);
+        assert_eq!(PriceOracle::twap(&state, 400, 300).unwrap(), 100);
+
+        // The oldest observations have been overwritten
+        assert_eq!(PriceOracle::twap(&state, 400, 350), Err(ContractError::InvalidAmount));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    errors::ContractError,
+    types::{LimitOrder, OrderSide},
+};
+
+pub struct LimitOrders;
+
+impl LimitOrders {
+    /// Validate a new order placed at `ledger`
+    pub fn validate_order(order: &LimitOrder, ledger: u32) -> Result<(), ContractError> {
+        if order.amount <= 0 || order.trigger_price <= 0 || order.min_out < 0 || order.executor_fee < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        // A buy pays the executor out of its escrow, so something must be left to spend
+        if order.side == OrderSide::Buy && order.executor_fee >= order.amount {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        if order.expiry_ledger < ledger {
+            return Err(ContractError::OrderExpired);
+        }
+
+        Ok(())
+    }
+
+    /// Check that an order may still execute at `ledger`
+    pub fn check_expiry(order: &LimitOrder, ledger: u32) -> Result<(), ContractError> {
+        if ledger > order.expiry_ledger {
+            return Err(ContractError::OrderExpired);
+        }
+
+        Ok(())
+    }
+
+    /// Check that the spot price has crossed an order's trigger
+    pub fn check_trigger(order: &LimitOrder, spot_price: i128) -> Result<(), ContractError> {
+        match order.side {
+            OrderSide::Buy if spot_price > order.trigger_price => Err(ContractError::TriggerNotReached),
+            OrderSide::Sell if spot_price < order.trigger_price => Err(ContractError::TriggerNotReached),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env};
+
+    fn create_order(env: &Env, side: OrderSide) -> LimitOrder {
+        LimitOrder {
+            owner: Address::generate(env),
+            side,
+            amount: 1_000_000,
+            trigger_price: 5_000,
+            min_out: 0,
+            executor_fee: 10_000,
+            expiry_ledger: 100,
+        }
+    }
+
+    #[test]
+    fn test_validate_order() {
+        let env = Env::default();
+        assert!(LimitOrders::validate_order(&create_order(&env, OrderSide::Buy), 100).is_ok());
+
+        let mut order = create_order(&env, OrderSide::Buy);
+        order.trigger_price = 0;
+        assert_eq!(LimitOrders::validate_order(&order, 0), Err(ContractError::InvalidAmount));
+
+        // Buy fees must leave something to spend, sell fees come out of the proceeds
+        let mut order = create_order(&env, OrderSide::Buy);
+        order.executor_fee = order.amount;
+        assert_eq!(LimitOrders::validate_order(&order, 0), Err(ContractError::InvalidAmount));
+        order.side = OrderSide::Sell;
+        assert!(LimitOrders::validate_order(&order, 0).is_ok());
+
+        let order = create_order(&env, OrderSide::Sell);
+        assert_eq!(LimitOrders::validate_order(&order, 101), Err(ContractError::OrderExpired));
+    }
+
+    #[test]
+    fn test_check_trigger() {
+        let env = Env::default();
+        let buy = create_order(&env, OrderSide::Buy);
+        assert!(LimitOrders::check_trigger(&buy, 4_999).is_ok());
+        assert!(LimitOrders::check_trigger(&buy, 5_000).is_ok());
+        assert_eq!(LimitOrders::check_trigger(&buy, 5_001), Err(ContractError::TriggerNotReached));
+
+        let sell = create_order(&env, OrderSide::Sell);
+        assert!(LimitOrders::check_trigger(&sell, 5_001).is_ok());
+        assert!(LimitOrders::check_trigger(&sell, 5_000).is_ok());
+        assert_eq!(LimitOrders::check_trigger(&sell, 4_999), Err(ContractError::TriggerNotReached));
+    }
+
+    #[test]
+    fn test_check_expiry() {
+        let env = Env::default();
+        let order = create_order(&env, OrderSide::Buy);
+        assert!(LimitOrders::check_expiry(&order, 100).is_ok());
+        assert_eq!(LimitOrders::check_expiry(&order, 101), Err(ContractError::OrderExpired));
+    }
+}
@@ -0,0 +1,142 @@
+use soroban_sdk::Map;
+use launchpad_types::BondingCurve;
+use crate::{
+    errors::ContractError,
+    types::{CurveParameters, CurvePreset},
+};
+
+const MAX_PRESETS: u32 = 32; // Keeps the registry small enough for instance storage
+
+pub struct CurvePresets;
+
+impl CurvePresets {
+    /// Validate a launch's supply, graduation thresholds and curve
+    pub fn validate_terms(
+        total_supply: i128,
+        launch_threshold: i128,
+        launch_threshold_percent: u32,
+        curve_params: &CurveParameters,
+    ) -> Result<(), ContractError> {
+        if total_supply <= 0 {
+            return Err(ContractError::InvalidSupply);
+        }
+
+        if launch_threshold <= 0 && launch_threshold_percent == 0 {
+            return Err(ContractError::InvalidLaunchThreshold);
+        }
+
+        if launch_threshold_percent > 100 {
+            return Err(ContractError::InvalidLaunchThreshold);
+        }
+
+        BondingCurve::validate_curve_params(curve_params, total_supply)?;
+        Ok(())
+    }
+
+    /// Validate a preset before it is added to or replaced in the registry
+    pub fn validate_preset(
+        presets: &Map<u32, CurvePreset>,
+        preset_id: u32,
+        preset: &CurvePreset,
+    ) -> Result<(), ContractError> {
+        if preset.name.is_empty() {
+            return Err(ContractError::InvalidCurveParameters);
+        }
+
+        if !presets.contains_key(preset_id) && presets.len() >= MAX_PRESETS {
+            return Err(ContractError::PresetRegistryFull);
+        }
+
+        Self::validate_terms(
+            preset.total_supply,
+            preset.launch_threshold,
+            preset.launch_threshold_percent,
+            &preset.curve_params,
+        )
+    }
+
+    /// Launch terms from a preset, with any non-zero argument taking precedence
+    ///
+    /// The two thresholds are taken together: both are used from the preset
+    /// unless either argument is set.
+    pub fn apply(
+        preset: CurvePreset,
+        total_supply: i128,
+        launch_threshold: i128,
+        launch_threshold_percent: u32,
+    ) -> (i128, i128, u32, CurveParameters) {
+        let total_supply = if total_supply == 0 { preset.total_supply } else { total_supply };
+        let (launch_threshold, launch_threshold_percent) = if launch_threshold == 0 && launch_threshold_percent == 0 {
+            (preset.launch_threshold, preset.launch_threshold_percent)
+        } else {
+            (launch_threshold, launch_threshold_percent)
+        };
+
+        (total_supply, launch_threshold, launch_threshold_percent, preset.curve_params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{Env, String};
+    use crate::types::CurveType;
+
+    fn create_preset(env: &Env) -> CurvePreset {
+        CurvePreset {
+            name: String::from_str(env, "Standard"),
+            curve_params: CurveParameters {
+                curve_type: CurveType::Linear,
+                base_price: 1000,
+                price_multiplier: 9000,
+            },
+            total_supply: 1_000_000,
+            launch_threshold: 100_000_000,
+            launch_threshold_percent: 80,
+        }
+    }
+
+    #[test]
+    fn test_validate_preset() {
+        let env = Env::default();
+        let mut presets = Map::new(&env);
+        assert!(CurvePresets::validate_preset(&presets, 0, &create_preset(&env)).is_ok());
+
+        let mut preset = create_preset(&env);
+        preset.name = String::from_str(&env, "");
+        let result = CurvePresets::validate_preset(&presets, 0, &preset);
+        assert_eq!(result, Err(ContractError::InvalidCurveParameters));
+
+        let mut preset = create_preset(&env);
+        preset.launch_threshold = 0;
+        preset.launch_threshold_percent = 0;
+        let result = CurvePresets::validate_preset(&presets, 0, &preset);
+        assert_eq!(result, Err(ContractError::InvalidLaunchThreshold));
+
+        let mut preset = create_preset(&env);
+        preset.total_supply = 0;
+        assert_eq!(CurvePresets::validate_preset(&presets, 0, &preset), Err(ContractError::InvalidSupply));
+
+        // A full registry only accepts replacements
+        for preset_id in 0..MAX_PRESETS {
+            presets.set(preset_id, create_preset(&env));
+        }
+        let result = CurvePresets::validate_preset(&presets, MAX_PRESETS, &create_preset(&env));
+        assert_eq!(result, Err(ContractError::PresetRegistryFull));
+        assert!(CurvePresets::validate_preset(&presets, 0, &create_preset(&env)).is_ok());
+    }
+
+    #[test]
+    fn test_apply_preset() {
+        let env = Env::default();
+        let preset = create_preset(&env);
+
+        let (supply, threshold, percent, curve) = CurvePresets::apply(preset.clone(), 0, 0, 0);
+        assert_eq!((supply, threshold, percent), (1_000_000, 100_000_000, 80));
+        assert_eq!(curve, preset.curve_params);
+
+        // Explicit arguments override the preset
+        let (supply, threshold, percent, _) = CurvePresets::apply(preset.clone(), 2_000_000, 0, 50);
+        assert_eq!((supply, threshold, percent), (2_000_000, 0, 50));
+    }
+}
@@ -0,0 +1,92 @@
+use soroban_sdk::Address;
+use crate::errors::ContractError;
+
+const MAX_REFERRAL_SHARE_BPS: u32 = 10000; // The whole platform fee
+
+pub struct ReferralProgram;
+
+impl ReferralProgram {
+    /// Validate the share of the platform fee paid to referrers
+    pub fn validate_share_bps(share_bps: u32) -> Result<(), ContractError> {
+        if share_bps > MAX_REFERRAL_SHARE_BPS {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        Ok(())
+    }
+
+    /// Check that `referrer` may refer `trader`
+    ///
+    /// Referrals are a single level: a referred wallet cannot refer others and a
+    /// referrer cannot later be referred. A trader keeps their first referrer.
+    pub fn check_referral(
+        trader: &Address,
+        referrer: &Address,
+        current_referrer: Option<Address>,
+        referrer_is_referred: bool,
+        trader_has_referrals: bool,
+    ) -> Result<(), ContractError> {
+        if trader == referrer {
+            return Err(ContractError::SelfReferral);
+        }
+
+        if referrer_is_referred || trader_has_referrals {
+            return Err(ContractError::ReferralChain);
+        }
+
+        match current_referrer {
+            Some(existing) if existing != *referrer => Err(ContractError::ReferrerMismatch),
+            _ => Ok(()),
+        }
+    }
+
+    /// Split a platform fee into the platform's part and the referrer's reward
+    pub fn split_fee(fee: i128, share_bps: u32) -> Result<(i128, i128), ContractError> {
+        let reward = fee
+            .checked_mul(share_bps as i128)
+            .map(|scaled| scaled / 10000)
+            .ok_or(ContractError::CalculationOverflow)?;
+
+        Ok((fee - reward, reward))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, Env};
+
+    #[test]
+    fn test_check_referral() {
+        let env = Env::default();
+        let trader = Address::generate(&env);
+        let referrer = Address::generate(&env);
+
+        assert!(ReferralProgram::check_referral(&trader, &referrer, None, false, false).is_ok());
+        assert!(ReferralProgram::check_referral(&trader, &referrer, Some(referrer.clone()), false, false).is_ok());
+
+        assert_eq!(
+            ReferralProgram::check_referral(&trader, &trader, None, false, false),
+            Err(ContractError::SelfReferral)
+        );
+        assert_eq!(
+            ReferralProgram::check_referral(&trader, &referrer, None, true, false),
+            Err(ContractError::ReferralChain)
+        );
+        assert_eq!(
+            ReferralProgram::check_referral(&trader, &referrer, None, false, true),
+            Err(ContractError::ReferralChain)
+        );
+        assert_eq!(
+            ReferralProgram::check_referral(&trader, &referrer, Some(Address::generate(&env)), false, false),
+            Err(ContractError::ReferrerMismatch)
+        );
+    }
+
+    #[test]
+    fn test_split_fee() {
+        assert_eq!(ReferralProgram::split_fee(100_000, 2500).unwrap(), (75_000, 25_000));
+        assert_eq!(ReferralProgram::split_fee(100_000, 0).unwrap(), (100_000, 0));
+        assert_eq!(ReferralProgram::split_fee(100_000, 10000).unwrap(), (0, 100_000));
+    }
+}
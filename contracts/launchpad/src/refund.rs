@@ -0,0 +1,40 @@
+use crate::errors::ContractError;
+
+pub struct Refund;
+
+impl Refund {
+    /// A holder's pro-rata share of a token's reserve for `amount` of `outstanding` tokens
+    ///
+    /// Each refund shrinks both the reserve and the outstanding supply, so
+    /// holders who withdraw later receive the same rate as earlier ones.
+    pub fn pro_rata_share(
+        reserve: i128,
+        amount: i128,
+        outstanding: i128,
+    ) -> Result<i128, ContractError> {
+        if amount <= 0 || amount > outstanding {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        reserve
+            .checked_mul(amount)
+            .map(|scaled| scaled / outstanding)
+            .ok_or(ContractError::CalculationOverflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pro_rata_share() {
+        assert_eq!(Refund::pro_rata_share(9_000_000, 2_500, 10_000).unwrap(), 2_250_000);
+
+        // The next holder sees the same rate on what is left
+        assert_eq!(Refund::pro_rata_share(6_750_000, 7_500, 7_500).unwrap(), 6_750_000);
+
+        assert_eq!(Refund::pro_rata_share(9_000_000, 10_001, 10_000), Err(ContractError::InvalidAmount));
+        assert_eq!(Refund::pro_rata_share(9_000_000, 0, 10_000), Err(ContractError::InvalidAmount));
+    }
+}
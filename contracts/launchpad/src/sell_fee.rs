@@ -0,0 +1,110 @@
+use crate::{
+    errors::ContractError,
+    types::SellFeeSchedule,
+};
+
+const MAX_SURCHARGE_BPS: u32 = 5000; // At most 50% of the proceeds on top of the platform fee
+
+pub struct SellFee;
+
+impl SellFee {
+    /// Validate a sell surcharge schedule
+    pub fn validate_schedule(schedule: &SellFeeSchedule) -> Result<(), ContractError> {
+        if schedule.max_surcharge_bps == 0 {
+            return Ok(());
+        }
+
+        if schedule.max_surcharge_bps > MAX_SURCHARGE_BPS || schedule.decay_ledgers == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        Ok(())
+    }
+
+    /// Surcharge on `proceeds`, falling linearly from the maximum to zero over the decay period
+    pub fn calculate_surcharge(
+        schedule: &SellFeeSchedule,
+        proceeds: i128,
+        held_ledgers: u32,
+    ) -> Result<i128, ContractError> {
+        if proceeds <= 0 || held_ledgers >= schedule.decay_ledgers {
+            return Ok(0);
+        }
+
+        let remaining = (schedule.decay_ledgers - held_ledgers) as i128;
+        proceeds
+            .checked_mul(schedule.max_surcharge_bps as i128)
+            .and_then(|scaled| scaled.checked_mul(remaining))
+            .map(|scaled| scaled / (10000 * schedule.decay_ledgers as i128))
+            .ok_or(ContractError::CalculationOverflow)
+    }
+
+    /// Balance-weighted average ledger of a holding after buying `amount` more at `ledger`
+    pub fn average_ledger(
+        balance: i128,
+        since_ledger: u32,
+        amount: i128,
+        ledger: u32,
+    ) -> Result<u32, ContractError> {
+        let total = balance.checked_add(amount).ok_or(ContractError::CalculationOverflow)?;
+        if total <= 0 {
+            return Ok(ledger);
+        }
+
+        let weighted = balance
+            .checked_mul(since_ledger as i128)
+            .and_then(|held| amount.checked_mul(ledger as i128).and_then(|bought| held.checked_add(bought)))
+            .ok_or(ContractError::CalculationOverflow)?;
+
+        Ok((weighted / total) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_schedule() -> SellFeeSchedule {
+        SellFeeSchedule {
+            max_surcharge_bps: 2000,
+            decay_ledgers: 100,
+            to_reserve: true,
+        }
+    }
+
+    #[test]
+    fn test_validate_schedule() {
+        assert!(SellFee::validate_schedule(&create_schedule()).is_ok());
+        assert!(SellFee::validate_schedule(&SellFeeSchedule::none()).is_ok());
+
+        let mut schedule = create_schedule();
+        schedule.decay_ledgers = 0;
+        assert_eq!(SellFee::validate_schedule(&schedule), Err(ContractError::InvalidAmount));
+
+        let mut schedule = create_schedule();
+        schedule.max_surcharge_bps = 5001;
+        assert_eq!(SellFee::validate_schedule(&schedule), Err(ContractError::InvalidAmount));
+    }
+
+    #[test]
+    fn test_calculate_surcharge() {
+        let schedule = create_schedule();
+        assert_eq!(SellFee::calculate_surcharge(&schedule, 1_000_000, 0), Ok(200_000));
+        assert_eq!(SellFee::calculate_surcharge(&schedule, 1_000_000, 25), Ok(150_000));
+        assert_eq!(SellFee::calculate_surcharge(&schedule, 1_000_000, 99), Ok(2_000));
+        assert_eq!(SellFee::calculate_surcharge(&schedule, 1_000_000, 100), Ok(0));
+        assert_eq!(SellFee::calculate_surcharge(&SellFeeSchedule::none(), 1_000_000, 0), Ok(0));
+    }
+
+    #[test]
+    fn test_average_ledger() {
+        // The first buy starts the clock
+        assert_eq!(SellFee::average_ledger(0, 0, 1_000, 50), Ok(50));
+
+        // Equal buys at ledgers 100 and 200 average to 150
+        assert_eq!(SellFee::average_ledger(1_000, 100, 1_000, 200), Ok(150));
+
+        // A small top-up barely moves a large holding
+        assert_eq!(SellFee::average_ledger(9_000, 100, 1_000, 200), Ok(110));
+    }
+}
@@ -1,70 +1,686 @@
-use soroban_sdk::{Address, Env, Vec};
-use crate::types::LaunchpadState;
+use soroban_sdk::{contracttype, Address, BytesN, Env, Map, String, Vec};
+use crate::types::{
+    Airdrop, AuctionState, BuyCommitment, CurvePreset, FeeConfig, Governance, HookNotice, LaunchHook, LaunchpadState,
+    LimitOrder, OracleState, PresaleConfig, QueuedAction, SellFeeSchedule, TokenInfo, TokenMetadata,
+    TradeLimits,
+};
 
 const DAY_IN_LEDGERS: u32 = 17280; // Approximately 24 hours
 const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS; // 7 days
 const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS; // 6 days
 
-// Storage keys
-const STATE_KEY: &str = "STATE";
-const TOKEN_COUNT_KEY: &str = "COUNT";
-const CREATOR_TOKENS_KEY: &str = "CREATOR";
-const RATE_LIMIT_KEY: &str = "RATE";
+/// Current storage layout; bump when keys or stored types change
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Keys of platform-wide settings in instance storage
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    SchemaVersion,
+    MigrationQueue,
+    Admin,
+    XlmToken,
+    FeeConfig,
+    TradeLimits,
+    ReferralShare,
+    KeeperBounty,
+    Shutdown,
+    Paused,
+    Governance,
+    ActionCount,
+    AirdropCount,
+    TokenCount,
+    CurvePresets,
+    CreationLocked,
+}
+
+/// Keys of per-token, per-account and per-id records
+#[derive(Clone)]
+#[contracttype]
+pub enum RecordKey {
+    State(Address),
+    CreatorTokens(Address),
+    RateLimit(Address),
+    Token(String),
+    TokenAt(u32),
+    Balance(String, Address),
+    Purchased(String, Address),
+    TokenTradeLimits(String),
+    LedgerOpenPrice(String),
+    Presale(String),
+    PresaleSpent(String, Address),
+    Auction(String),
+    Commitment(String, Address),
+    BuyCommitment(Address, BytesN<32>),
+    Referrer(Address),
+    HasReferrals(Address),
+    ReferralRewards(Address, Address),
+    QueuedAction(u32),
+    Airdrop(u32),
+    AirdropBitmap(u32, u32),
+    Oracle(String),
+    LaunchHook(String),
+    Metadata(String),
+    OrderCount(String),
+    LimitOrder(String, u32),
+    SellFee(String),
+    HoldingLedger(String, Address),
+    OrderEscrow(String, Address),
+    HookQueue(String),
+    HookNotice(String, u32),
+    LegacyTokenAt(u32),
+}
+
+/// Schema v0 layout, kept to migrate records written before `SCHEMA_VERSION` 1
+///
+/// Token records were stored under the bare token id and launchpad state under
+/// the bare token address. Nothing was kept in instance storage, and no key
+/// listed the tokens.
+pub mod legacy {
+    use soroban_sdk::{contracttype, Address, String};
+    use crate::types::CurveParameters;
+
+    pub const TOKEN_COUNT: &str = "COUNT";
+    pub const CREATOR_TOKENS: &str = "CREATOR";
+    pub const RATE_LIMIT: &str = "RATE";
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    #[contracttype(export = false)]
+    pub struct LaunchpadState {
+        pub token_address: Address,
+        pub distribution_account: Address,
+        pub total_supply: i128,
+        pub tokens_sold: i128,
+        pub xlm_raised: i128,
+        pub launch_threshold_xlm: i128,
+        pub launch_threshold_percent: u32,
+        pub is_launched: bool,
+        pub curve_params: CurveParameters,
+        pub creator: Address,
+        pub creation_time: u64,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    #[contracttype(export = false)]
+    pub struct TokenInfo {
+        pub name: String,
+        pub symbol: String,
+        pub total_supply: i128,
+        pub tokens_sold: i128,
+        pub xlm_raised: i128,
+        pub current_price: i128,
+        pub launch_progress_percent: u32,
+        pub is_launched: bool,
+        pub creator: Address,
+        pub creation_time: u64,
+    }
+}
+
+/// Get the storage layout version, 0 for records written before versioning
+pub fn get_schema_version(env: &Env) -> u32 {
+    env.storage().instance().get(&DataKey::SchemaVersion).unwrap_or(0)
+}
+
+/// Set the storage layout version
+pub fn set_schema_version(env: &Env, version: u32) {
+    env.storage().instance().set(&DataKey::SchemaVersion, &version);
+}
+
+/// Get the `(head, tail)` positions of the schema v0 tokens queued for migration
+pub fn get_migration_queue(env: &Env) -> (u32, u32) {
+    env.storage().instance().get(&DataKey::MigrationQueue).unwrap_or((0, 0))
+}
+
+/// Queue a schema v0 token id for migration
+pub fn push_legacy_token(env: &Env, token_id: &String) {
+    let (head, tail) = get_migration_queue(env);
+    env.storage().persistent().set(&RecordKey::LegacyTokenAt(tail), token_id);
+    env.storage().instance().set(&DataKey::MigrationQueue, &(head, tail + 1));
+}
+
+/// Take the next schema v0 token id queued for migration
+pub fn pop_legacy_token(env: &Env) -> Option<String> {
+    let (head, tail) = get_migration_queue(env);
+    if head == tail {
+        return None;
+    }
+
+    let key = RecordKey::LegacyTokenAt(head);
+    let token_id = env.storage().persistent().get(&key);
+    env.storage().persistent().remove(&key);
+    env.storage().instance().set(&DataKey::MigrationQueue, &(head + 1, tail));
+    token_id
+}
 
 /// Extend the TTL for state storage
 pub fn extend_state_ttl(env: &Env, token_address: &Address) {
     env.storage()
         .persistent()
-        .extend_ttl(token_address, INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        .extend_ttl(&RecordKey::State(token_address.clone()), INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 }
 
 /// Store launchpad state for a token
 pub fn set_state(env: &Env, token_address: &Address, state: &LaunchpadState) {
-    env.storage().persistent().set(token_address, state);
+    env.storage().persistent().set(&RecordKey::State(token_address.clone()), state);
 }
 
 /// Get launchpad state for a token
 pub fn get_state(env: &Env, token_address: &Address) -> Option<LaunchpadState> {
-    env.storage().persistent().get(token_address)
+    env.storage().persistent().get(&RecordKey::State(token_address.clone()))
+}
+
+/// Store a token's record
+pub fn set_token(env: &Env, token_id: &String, token_info: &TokenInfo) {
+    env.storage().persistent().set(&RecordKey::Token(token_id.clone()), token_info);
+}
+
+/// Get a token's record
+pub fn get_token(env: &Env, token_id: &String) -> Option<TokenInfo> {
+    env.storage().persistent().get(&RecordKey::Token(token_id.clone()))
+}
+
+/// Check whether a token exists
+pub fn has_token(env: &Env, token_id: &String) -> bool {
+    env.storage().persistent().has(&RecordKey::Token(token_id.clone()))
+}
+
+/// Append a token to the index of all tokens
+pub fn add_token(env: &Env, token_id: &String) {
+    let index = get_token_count(env);
+    env.storage().persistent().set(&RecordKey::TokenAt(index), token_id);
+    increment_token_count(env);
+}
+
+/// Get the id of the token at `index` in creation order
+pub fn get_token_at(env: &Env, index: u32) -> Option<String> {
+    env.storage().persistent().get(&RecordKey::TokenAt(index))
 }
 
 /// Get total token count
 pub fn get_token_count(env: &Env) -> u32 {
-    env.storage().persistent().get(&TOKEN_COUNT_KEY).unwrap_or(0)
+    env.storage().instance().get(&DataKey::TokenCount).unwrap_or(0)
 }
 
 /// Increment token count
 pub fn increment_token_count(env: &Env) {
     let current_count = get_token_count(env);
-    env.storage().persistent().set(&TOKEN_COUNT_KEY, &(current_count + 1));
+    env.storage().instance().set(&DataKey::TokenCount, &(current_count + 1));
 }
 
 /// Add token to creator's list
 pub fn add_creator_token(env: &Env, creator: &Address, token_address: &Address) {
-    let key = (CREATOR_TOKENS_KEY, creator);
-    let mut tokens: Vec<Address> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+    let mut tokens = get_creator_tokens(env, creator);
     tokens.push_back(token_address.clone());
-    env.storage().persistent().set(&key, &tokens);
+    set_creator_tokens(env, creator, &tokens);
+}
+
+/// Replace the tokens listed for a creator
+pub fn set_creator_tokens(env: &Env, creator: &Address, tokens: &Vec<Address>) {
+    env.storage().persistent().set(&RecordKey::CreatorTokens(creator.clone()), tokens);
 }
 
 /// Get tokens created by a creator
 pub fn get_creator_tokens(env: &Env, creator: &Address) -> Vec<Address> {
-    let key = (CREATOR_TOKENS_KEY, creator);
-    env.storage().persistent().get(&key).unwrap_or(Vec::new(env))
+    env.storage().persistent().get(&RecordKey::CreatorTokens(creator.clone()))
+        .unwrap_or(Vec::new(env))
 }
 
 /// Set rate limit timestamp for creator
 pub fn set_rate_limit_timestamp(env: &Env, creator: &Address, timestamp: u64) {
-    let key = (RATE_LIMIT_KEY, creator);
-    env.storage().persistent().set(&key, &timestamp);
+    env.storage().persistent().set(&RecordKey::RateLimit(creator.clone()), &timestamp);
 }
 
 /// Get rate limit timestamp for creator
 pub fn get_rate_limit_timestamp(env: &Env, creator: &Address) -> u64 {
-    let key = (RATE_LIMIT_KEY, creator);
+    env.storage().persistent().get(&RecordKey::RateLimit(creator.clone())).unwrap_or(0)
+}
+
+/// Get a holder's token balance
+pub fn get_balance(env: &Env, token_id: &String, holder: &Address) -> i128 {
+    let key = RecordKey::Balance(token_id.clone(), holder.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set a holder's token balance
+pub fn set_balance(env: &Env, token_id: &String, holder: &Address, balance: i128) {
+    let key = RecordKey::Balance(token_id.clone(), holder.clone());
+    env.storage().persistent().set(&key, &balance);
+}
+
+/// Get the tokens a wallet has bought from the curve
+pub fn get_purchased(env: &Env, token_id: &String, buyer: &Address) -> i128 {
+    let key = RecordKey::Purchased(token_id.clone(), buyer.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set the tokens a wallet has bought from the curve
+pub fn set_purchased(env: &Env, token_id: &String, buyer: &Address, amount: i128) {
+    let key = RecordKey::Purchased(token_id.clone(), buyer.clone());
+    env.storage().persistent().set(&key, &amount);
+}
+
+/// Check whether the contract has an admin
+pub fn has_admin(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::Admin)
+}
+
+/// Set the platform admin
+pub fn set_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, admin);
+}
+
+/// Get the platform admin
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+/// Set the native XLM asset contract
+pub fn set_xlm_token(env: &Env, xlm_token: &Address) {
+    env.storage().instance().set(&DataKey::XlmToken, xlm_token);
+}
+
+/// Get the native XLM asset contract
+pub fn get_xlm_token(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::XlmToken)
+}
+
+/// Set the platform fee configuration
+pub fn set_fee_config(env: &Env, fee_config: &FeeConfig) {
+    env.storage().instance().set(&DataKey::FeeConfig, fee_config);
+}
+
+/// Get the platform fee configuration
+pub fn get_fee_config(env: &Env) -> Option<FeeConfig> {
+    env.storage().instance().get(&DataKey::FeeConfig)
+}
+
+/// Set the platform-wide trade limits
+pub fn set_trade_limits(env: &Env, limits: &TradeLimits) {
+    env.storage().instance().set(&DataKey::TradeLimits, limits);
+}
+
+/// Get the platform-wide trade limits
+pub fn get_trade_limits(env: &Env) -> TradeLimits {
+    env.storage().instance().get(&DataKey::TradeLimits).unwrap_or_else(TradeLimits::none)
+}
+
+/// Set or clear a token's trade limit override
+pub fn set_token_trade_limits(env: &Env, token_id: &String, limits: &Option<TradeLimits>) {
+    let key = RecordKey::TokenTradeLimits(token_id.clone());
+    match limits {
+        Some(limits) => env.storage().persistent().set(&key, limits),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get a token's trade limit override
+pub fn get_token_trade_limits(env: &Env, token_id: &String) -> Option<TradeLimits> {
+    env.storage().persistent().get(&RecordKey::TokenTradeLimits(token_id.clone()))
+}
+
+/// Record the price a token opened at in the current ledger
+pub fn set_ledger_open_price(env: &Env, token_id: &String, ledger: u32, price: i128) {
+    let key = RecordKey::LedgerOpenPrice(token_id.clone());
+    env.storage().temporary().set(&key, &(ledger, price));
+}
+
+/// Get the ledger and opening price of a token's latest traded ledger
+pub fn get_ledger_open_price(env: &Env, token_id: &String) -> Option<(u32, i128)> {
+    env.storage().temporary().get(&RecordKey::LedgerOpenPrice(token_id.clone()))
+}
+
+/// Store a token's presale configuration
+pub fn set_presale(env: &Env, token_id: &String, presale: &PresaleConfig) {
+    env.storage().persistent().set(&RecordKey::Presale(token_id.clone()), presale);
+}
+
+/// Get a token's presale configuration
+pub fn get_presale(env: &Env, token_id: &String) -> Option<PresaleConfig> {
+    env.storage().persistent().get(&RecordKey::Presale(token_id.clone()))
+}
+
+/// Get the XLM a buyer has spent in a token's presale
+pub fn get_presale_spent(env: &Env, token_id: &String, buyer: &Address) -> i128 {
+    let key = RecordKey::PresaleSpent(token_id.clone(), buyer.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set the XLM a buyer has spent in a token's presale
+pub fn set_presale_spent(env: &Env, token_id: &String, buyer: &Address, amount: i128) {
+    let key = RecordKey::PresaleSpent(token_id.clone(), buyer.clone());
+    env.storage().persistent().set(&key, &amount);
+}
+
+/// Store a token's opening auction state
+pub fn set_auction(env: &Env, token_id: &String, auction: &AuctionState) {
+    env.storage().persistent().set(&RecordKey::Auction(token_id.clone()), auction);
+}
+
+/// Get a token's opening auction state
+pub fn get_auction(env: &Env, token_id: &String) -> Option<AuctionState> {
+    env.storage().persistent().get(&RecordKey::Auction(token_id.clone()))
+}
+
+/// Get the XLM a buyer has committed to a token's auction
+pub fn get_commitment(env: &Env, token_id: &String, buyer: &Address) -> i128 {
+    let key = RecordKey::Commitment(token_id.clone(), buyer.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set the XLM a buyer has committed to a token's auction
+pub fn set_commitment(env: &Env, token_id: &String, buyer: &Address, amount: i128) {
+    let key = RecordKey::Commitment(token_id.clone(), buyer.clone());
+    env.storage().persistent().set(&key, &amount);
+}
+
+/// Remove a buyer's auction commitment once claimed
+pub fn remove_commitment(env: &Env, token_id: &String, buyer: &Address) {
+    let key = RecordKey::Commitment(token_id.clone(), buyer.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Store a buyer's hidden buy commitment under its hash
+pub fn set_buy_commitment(env: &Env, buyer: &Address, hash: &BytesN<32>, commitment: &BuyCommitment) {
+    let key = RecordKey::BuyCommitment(buyer.clone(), hash.clone());
+    env.storage().persistent().set(&key, commitment);
+}
+
+/// Get a buyer's hidden buy commitment by its hash
+pub fn get_buy_commitment(env: &Env, buyer: &Address, hash: &BytesN<32>) -> Option<BuyCommitment> {
+    let key = RecordKey::BuyCommitment(buyer.clone(), hash.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Remove a revealed or reclaimed buy commitment
+pub fn remove_buy_commitment(env: &Env, buyer: &Address, hash: &BytesN<32>) {
+    let key = RecordKey::BuyCommitment(buyer.clone(), hash.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Set the share of the platform fee paid to referrers, in basis points
+pub fn set_referral_share_bps(env: &Env, share_bps: u32) {
+    env.storage().instance().set(&DataKey::ReferralShare, &share_bps);
+}
+
+/// Get the share of the platform fee paid to referrers, in basis points
+pub fn get_referral_share_bps(env: &Env) -> u32 {
+    env.storage().instance().get(&DataKey::ReferralShare).unwrap_or(0)
+}
+
+/// Set the wallet that referred a trader
+pub fn set_referrer(env: &Env, trader: &Address, referrer: &Address) {
+    env.storage().persistent().set(&RecordKey::Referrer(trader.clone()), referrer);
+}
+
+/// Get the wallet that referred a trader
+pub fn get_referrer(env: &Env, trader: &Address) -> Option<Address> {
+    env.storage().persistent().get(&RecordKey::Referrer(trader.clone()))
+}
+
+/// Mark a wallet as having referred other traders
+pub fn set_has_referrals(env: &Env, referrer: &Address) {
+    env.storage().persistent().set(&RecordKey::HasReferrals(referrer.clone()), &true);
+}
+
+/// Check whether a wallet has referred other traders
+pub fn has_referrals(env: &Env, referrer: &Address) -> bool {
+    env.storage().persistent().get(&RecordKey::HasReferrals(referrer.clone()))
+        .unwrap_or(false)
+}
+
+/// Get a referrer's unclaimed rewards in one quote asset
+pub fn get_referral_rewards(env: &Env, referrer: &Address, quote_asset: &Address) -> i128 {
+    let key = RecordKey::ReferralRewards(referrer.clone(), quote_asset.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set a referrer's unclaimed rewards in one quote asset
+pub fn set_referral_rewards(env: &Env, referrer: &Address, quote_asset: &Address, amount: i128) {
+    let key = RecordKey::ReferralRewards(referrer.clone(), quote_asset.clone());
+    env.storage().persistent().set(&key, &amount);
+}
+
+/// Permanently shut the platform down
+pub fn set_shutdown(env: &Env) {
+    env.storage().instance().set(&DataKey::Shutdown, &true);
+}
+
+/// Check whether the platform has been shut down
+pub fn is_shutdown(env: &Env) -> bool {
+    env.storage().instance().get(&DataKey::Shutdown).unwrap_or(false)
+}
+
+/// Stop new tokens from being created
+pub fn set_creation_locked(env: &Env) {
+    env.storage().instance().set(&DataKey::CreationLocked, &true);
+}
+
+/// Check whether token creation has been locked
+pub fn is_creation_locked(env: &Env) -> bool {
+    env.storage().instance().get(&DataKey::CreationLocked).unwrap_or(false)
+}
+
+/// Pause or unpause trading
+pub fn set_paused(env: &Env, paused: bool) {
+    env.storage().instance().set(&DataKey::Paused, &paused);
+}
+
+/// Check whether trading is paused
+pub fn is_paused(env: &Env) -> bool {
+    env.storage().instance().get(&DataKey::Paused).unwrap_or(false)
+}
+
+/// Set the M-of-N admin set
+pub fn set_governance(env: &Env, governance: &Governance) {
+    env.storage().instance().set(&DataKey::Governance, governance);
+}
+
+/// Get the M-of-N admin set, if one replaced the single admin
+pub fn get_governance(env: &Env) -> Option<Governance> {
+    env.storage().instance().get(&DataKey::Governance)
+}
+
+/// Get the number of admin actions ever queued
+pub fn get_action_count(env: &Env) -> u32 {
+    env.storage().instance().get(&DataKey::ActionCount).unwrap_or(0)
+}
+
+/// Reserve the next queued action id
+pub fn next_action_id(env: &Env) -> u32 {
+    let id = get_action_count(env);
+    env.storage().instance().set(&DataKey::ActionCount, &(id + 1));
+    id
+}
+
+/// Set a queued admin action
+pub fn set_queued_action(env: &Env, action_id: u32, queued: &QueuedAction) {
+    env.storage().persistent().set(&RecordKey::QueuedAction(action_id), queued);
+}
+
+/// Get a queued admin action
+pub fn get_queued_action(env: &Env, action_id: u32) -> Option<QueuedAction> {
+    env.storage().persistent().get(&RecordKey::QueuedAction(action_id))
+}
+
+/// Remove an executed or cancelled admin action
+pub fn remove_queued_action(env: &Env, action_id: u32) {
+    env.storage().persistent().remove(&RecordKey::QueuedAction(action_id));
+}
+
+/// Set the keeper bounty paid from a token's reserve, in basis points
+pub fn set_keeper_bounty_bps(env: &Env, bounty_bps: u32) {
+    env.storage().instance().set(&DataKey::KeeperBounty, &bounty_bps);
+}
+
+/// Get the keeper bounty paid from a token's reserve, in basis points
+pub fn get_keeper_bounty_bps(env: &Env) -> u32 {
+    env.storage().instance().get(&DataKey::KeeperBounty).unwrap_or(0)
+}
+
+/// Get the number of airdrops ever created
+pub fn get_airdrop_count(env: &Env) -> u32 {
+    env.storage().instance().get(&DataKey::AirdropCount).unwrap_or(0)
+}
+
+/// Reserve the next airdrop id
+pub fn next_airdrop_id(env: &Env) -> u32 {
+    let id = get_airdrop_count(env);
+    env.storage().instance().set(&DataKey::AirdropCount, &(id + 1));
+    id
+}
+
+/// Set an airdrop by id
+pub fn set_airdrop(env: &Env, airdrop_id: u32, airdrop: &Airdrop) {
+    env.storage().persistent().set(&RecordKey::Airdrop(airdrop_id), airdrop);
+}
+
+/// Get an airdrop by id
+pub fn get_airdrop(env: &Env, airdrop_id: u32) -> Option<Airdrop> {
+    env.storage().persistent().get(&RecordKey::Airdrop(airdrop_id))
+}
+
+/// Get one 128-index word of an airdrop's claim bitmap
+pub fn get_airdrop_bitmap(env: &Env, airdrop_id: u32, word: u32) -> u128 {
+    let key = RecordKey::AirdropBitmap(airdrop_id, word);
     env.storage().persistent().get(&key).unwrap_or(0)
 }
 
+/// Set one 128-index word of an airdrop's claim bitmap
+pub fn set_airdrop_bitmap(env: &Env, airdrop_id: u32, word: u32, bits: u128) {
+    let key = RecordKey::AirdropBitmap(airdrop_id, word);
+    env.storage().persistent().set(&key, &bits);
+}
+
+/// Set a token's price oracle observations
+pub fn set_oracle(env: &Env, token_id: &String, oracle: &OracleState) {
+    env.storage().persistent().set(&RecordKey::Oracle(token_id.clone()), oracle);
+}
+
+/// Get a token's price oracle observations
+pub fn get_oracle(env: &Env, token_id: &String) -> Option<OracleState> {
+    env.storage().persistent().get(&RecordKey::Oracle(token_id.clone()))
+}
+
+/// Set or remove a token's launch hook
+pub fn set_launch_hook(env: &Env, token_id: &String, hook: &Option<LaunchHook>) {
+    let key = RecordKey::LaunchHook(token_id.clone());
+    match hook {
+        Some(hook) => env.storage().persistent().set(&key, hook),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get a token's launch hook
+pub fn get_launch_hook(env: &Env, token_id: &String) -> Option<LaunchHook> {
+    env.storage().persistent().get(&RecordKey::LaunchHook(token_id.clone()))
+}
+
+/// Get the `(head, tail)` ids of a token's queued hook notifications
+pub fn get_hook_queue(env: &Env, token_id: &String) -> (u32, u32) {
+    env.storage().persistent().get(&RecordKey::HookQueue(token_id.clone())).unwrap_or((0, 0))
+}
+
+/// Queue a notification for a token's launch hook
+pub fn push_hook_notice(env: &Env, token_id: &String, notice: &HookNotice) {
+    let (head, tail) = get_hook_queue(env, token_id);
+    env.storage().persistent().set(&RecordKey::HookNotice(token_id.clone(), tail), notice);
+    env.storage().persistent().set(&RecordKey::HookQueue(token_id.clone()), &(head, tail + 1));
+}
+
+/// Take the oldest queued notification for a token's launch hook
+pub fn pop_hook_notice(env: &Env, token_id: &String) -> Option<HookNotice> {
+    let (head, tail) = get_hook_queue(env, token_id);
+    if head == tail {
+        return None;
+    }
+
+    let key = RecordKey::HookNotice(token_id.clone(), head);
+    let notice = env.storage().persistent().get(&key);
+    env.storage().persistent().remove(&key);
+    env.storage().persistent().set(&RecordKey::HookQueue(token_id.clone()), &(head + 1, tail));
+    notice
+}
+
+/// Set a token's metadata
+pub fn set_metadata(env: &Env, token_id: &String, metadata: &TokenMetadata) {
+    env.storage().persistent().set(&RecordKey::Metadata(token_id.clone()), metadata);
+}
+
+/// Get a token's metadata
+pub fn get_metadata(env: &Env, token_id: &String) -> Option<TokenMetadata> {
+    env.storage().persistent().get(&RecordKey::Metadata(token_id.clone()))
+}
+
+/// Set the registry of curve presets
+pub fn set_curve_presets(env: &Env, presets: &Map<u32, CurvePreset>) {
+    env.storage().instance().set(&DataKey::CurvePresets, presets);
+}
+
+/// Get the registry of curve presets
+pub fn get_curve_presets(env: &Env) -> Map<u32, CurvePreset> {
+    env.storage().instance().get(&DataKey::CurvePresets).unwrap_or(Map::new(env))
+}
+
+/// Reserve the next limit order id for a token
+pub fn next_order_id(env: &Env, token_id: &String) -> u32 {
+    let key = RecordKey::OrderCount(token_id.clone());
+    let id: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(id + 1));
+    id
+}
+
+/// Set an open limit order
+pub fn set_limit_order(env: &Env, token_id: &String, order_id: u32, order: &LimitOrder) {
+    env.storage().persistent().set(&RecordKey::LimitOrder(token_id.clone(), order_id), order);
+}
+
+/// Get an open limit order
+pub fn get_limit_order(env: &Env, token_id: &String, order_id: u32) -> Option<LimitOrder> {
+    env.storage().persistent().get(&RecordKey::LimitOrder(token_id.clone(), order_id))
+}
+
+/// Remove a filled or cancelled limit order
+pub fn remove_limit_order(env: &Env, token_id: &String, order_id: u32) {
+    env.storage().persistent().remove(&RecordKey::LimitOrder(token_id.clone(), order_id));
+}
+
+/// Set the tokens a holder has escrowed in open sell orders
+pub fn set_order_escrow(env: &Env, token_id: &String, owner: &Address, amount: i128) {
+    let key = RecordKey::OrderEscrow(token_id.clone(), owner.clone());
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &amount);
+    }
+}
+
+/// Get the tokens a holder has escrowed in open sell orders
+pub fn get_order_escrow(env: &Env, token_id: &String, owner: &Address) -> i128 {
+    let key = RecordKey::OrderEscrow(token_id.clone(), owner.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set a token's sell surcharge schedule
+pub fn set_sell_fee(env: &Env, token_id: &String, schedule: &SellFeeSchedule) {
+    env.storage().persistent().set(&RecordKey::SellFee(token_id.clone()), schedule);
+}
+
+/// Get a token's sell surcharge schedule, if it opted in
+pub fn get_sell_fee(env: &Env, token_id: &String) -> Option<SellFeeSchedule> {
+    env.storage().persistent().get(&RecordKey::SellFee(token_id.clone()))
+}
+
+/// Set a holder's balance-weighted average buy ledger
+pub fn set_holding_ledger(env: &Env, token_id: &String, holder: &Address, ledger: u32) {
+    let key = RecordKey::HoldingLedger(token_id.clone(), holder.clone());
+    env.storage().persistent().set(&key, &ledger);
+}
+
+/// Get a holder's balance-weighted average buy ledger
+pub fn get_holding_ledger(env: &Env, token_id: &String, holder: &Address) -> Option<u32> {
+    env.storage().persistent().get(&RecordKey::HoldingLedger(token_id.clone(), holder.clone()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,8 +693,9 @@ mod tests {
             distribution_account: Address::generate(env),
             total_supply: 1_000_000,
             tokens_sold: 0,
-            xlm_raised: 0,
-            launch_threshold_xlm: 100_000_000,
+            quote_asset: Address::generate(env),
+            quote_raised: 0,
+            launch_threshold: 100_000_000,
             launch_threshold_percent: 80,
             is_launched: false,
             curve_params: CurveParameters {
@@ -201,4 +818,4 @@ mod tests {
         let timestamp = get_rate_limit_timestamp(&env, &fake_address);
         assert_eq!(timestamp, 0);
     }
-}
\ No newline at end of file
+}
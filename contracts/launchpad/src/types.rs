@@ -1,19 +1,6 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[contracttype]
-pub enum CurveType {
-    Linear,
-    Quadratic,
-}
-
-#[derive(Clone, Debug, Eq, PartialEq)]
-#[contracttype]
-pub struct CurveParameters {
-    pub curve_type: CurveType,
-    pub base_price: i128,        // Starting price in stroops
-    pub price_multiplier: i128,  // Price scaling factor
-}
+pub use launchpad_types::{CurveParameters, CurveType};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
@@ -22,8 +9,9 @@ pub struct LaunchpadState {
     pub distribution_account: Address,    // Account holding token supply
     pub total_supply: i128,              // Fixed token supply
     pub tokens_sold: i128,               // Tokens sold via bonding curve
-    pub xlm_raised: i128,                // Total XLM collected
-    pub launch_threshold_xlm: i128,      // XLM target for launch
+    pub quote_asset: Address,            // Asset contract the curve trades against
+    pub quote_raised: i128,              // Total quote asset collected
+    pub launch_threshold: i128,          // Quote asset target for launch
     pub launch_threshold_percent: u32,   // % of supply target for launch
     pub is_launched: bool,               // Irreversible launch flag
     pub curve_params: CurveParameters,   // Bonding curve configuration
@@ -31,6 +19,224 @@ pub struct LaunchpadState {
     pub creation_time: u64,              // Launch timestamp
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LaunchProtection {
+    pub window_ledgers: u32,                 // Ledgers after creation with a per-wallet buy cap
+    pub max_buy_per_wallet: Option<i128>,    // Tokens one wallet may buy inside the window
+    pub max_wallet_percent: Option<u32>,     // % of supply one wallet may hold before launch
+    pub max_dev_buy: Option<i128>,           // Tokens the creator may buy before launch
+}
+
+impl LaunchProtection {
+    /// Protection with every limit disabled
+    pub fn none() -> Self {
+        LaunchProtection {
+            window_ledgers: 0,
+            max_buy_per_wallet: None,
+            max_wallet_percent: None,
+            max_dev_buy: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TradeLimits {
+    pub max_trade_impact_bps: Option<u32>,   // Max price move of a single trade
+    pub max_ledger_move_bps: Option<u32>,    // Max price move within one ledger
+}
+
+impl TradeLimits {
+    /// Limits with the circuit breaker disabled
+    pub fn none() -> Self {
+        TradeLimits {
+            max_trade_impact_bps: None,
+            max_ledger_move_bps: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PresaleConfig {
+    pub merkle_root: BytesN<32>,             // Root of (address, max XLM allocation) leaves
+    pub end_ledger: u32,                     // Ledger public trading opens
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AuctionConfig {
+    pub duration_ledgers: u32,               // Ledgers commitments are collected for
+    pub max_tokens: i128,                    // Most tokens the auction may sell
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AuctionState {
+    pub end_ledger: u32,                     // Ledger commitments close
+    pub max_tokens: i128,                    // Most tokens the auction may sell
+    pub total_committed: i128,               // Quote asset committed by all participants
+    pub settled: bool,                       // Clearing price has been computed
+    pub tokens_cleared: i128,                // Tokens sold at the clearing price
+    pub quote_cleared: i128,                 // Quote asset kept at the clearing price
+    pub clearing_price: i128,                // Uniform price paid per token
+    pub committed_claimed: i128,             // Commitments already claimed, for cumulative rounding
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AuctionClaim {
+    pub tokens_received: i128,
+    pub quote_refunded: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum LaunchMode {
+    Open,                                    // Curve trading from creation
+    Presale(PresaleConfig),                  // Allowlisted buyers first
+    Auction(AuctionConfig),                  // Opening batch auction first
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LaunchOptions {
+    pub protection: LaunchProtection,        // Anti-sniping limits
+    pub mode: LaunchMode,                    // How trading opens
+    pub creator_allocation: CreatorAllocation, // Supply reserved for the creator
+    pub launch_deadline_ledgers: u32,        // Ledgers to launch before refunds open, 0 for none
+    pub hook: Option<Address>,               // Contract notified of launch lifecycle events
+    pub hook_trades: bool,                   // Whether the hook is also notified of each trade
+    pub sell_fee: SellFeeSchedule,           // Surcharge on sales by recent buyers
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CurvePreset {
+    pub name: String,                        // Shown to creators, e.g. "Standard"
+    pub curve_params: CurveParameters,
+    pub total_supply: i128,
+    pub launch_threshold: i128,              // Quote asset raised to graduate
+    pub launch_threshold_percent: u32,       // Or percent of the curve supply sold
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum CurveSource {
+    Custom(CurveParameters),                 // Creator-supplied curve
+    Preset(u32),                             // Admin-curated preset id
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum OrderSide {
+    Buy,                                     // Buy once the spot price falls to the trigger
+    Sell,                                    // Sell once the spot price rises to the trigger
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LimitOrder {
+    pub owner: Address,
+    pub side: OrderSide,
+    pub amount: i128,                        // Quote escrowed for a buy, tokens for a sell
+    pub trigger_price: i128,                 // Spot price the order waits for
+    pub min_out: i128,                       // Tokens or quote the fill must deliver to the owner
+    pub executor_fee: i128,                  // Quote paid to the executor out of the fill
+    pub expiry_ledger: u32,                  // Last ledger the order may execute
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TokenMetadata {
+    pub image_uri: String,                   // e.g. an ipfs:// or https:// image link
+    pub description: String,
+    pub website: String,                     // Empty if the project has none
+    pub socials: Vec<String>,                // Social profile links
+    pub content_hash: BytesN<32>,            // Hash of the full off-chain metadata document
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SellFeeSchedule {
+    pub max_surcharge_bps: u32,              // Added to the platform fee on a sale in the buy ledger, 0 to disable
+    pub decay_ledgers: u32,                  // Holding period over which the surcharge falls to zero
+    pub to_reserve: bool,                    // Keep the surcharge in the reserve instead of paying the fee recipient
+}
+
+impl SellFeeSchedule {
+    /// No sell surcharge
+    pub fn none() -> Self {
+        SellFeeSchedule {
+            max_surcharge_bps: 0,
+            decay_ledgers: 0,
+            to_reserve: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LaunchHook {
+    pub contract: Address,                   // Hook contract implementing `LaunchHookInterface`
+    pub on_trade: bool,                      // Whether to call `on_trade` after each trade
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum HookNotice {
+    Graduate(i128, i128, i128),              // Quote raised, tokens sold, price
+    Trade(Address, bool, i128, i128, i128),  // Trader, is buy, token amount, quote amount, price
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CreatorAllocation {
+    pub percent: u32,                        // % of total supply reserved for the creator
+    pub cliff_ledgers: u32,                  // Ledgers after graduation before anything vests
+    pub vesting_ledgers: u32,                // Ledgers after graduation until fully vested
+}
+
+impl CreatorAllocation {
+    /// No supply reserved for the creator
+    pub fn none() -> Self {
+        CreatorAllocation {
+            percent: 0,
+            cliff_ledgers: 0,
+            vesting_ledgers: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Airdrop {
+    pub token_id: String,
+    pub creator: Address,
+    pub merkle_root: BytesN<32>,             // Root of (index, address, amount) leaves
+    pub total_amount: i128,                  // Tokens funded by the creator
+    pub claimed_amount: i128,                // Tokens claimed by recipients
+    pub expiry_ledger: u32,                  // First ledger claims are closed
+    pub reclaimed: bool,                     // Unclaimed tokens returned to the creator
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct BuyCommitment {
+    pub buyer: Address,
+    pub token_id: String,
+    pub escrowed_xlm: i128,                  // Quote asset locked until reveal or reclaim
+    pub commit_ledger: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeConfig {
+    pub fee_bps: u32,                        // Platform fee on trades in basis points
+    pub fee_recipient: Address,              // Account platform fees are paid to
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct TokenInfo {
@@ -38,18 +244,34 @@ pub struct TokenInfo {
     pub symbol: String,
     pub total_supply: i128,
     pub tokens_sold: i128,
-    pub xlm_raised: i128,
+    pub quote_asset: Address,
+    pub quote_raised: i128,
+    pub launch_threshold: i128,
+    pub launch_threshold_percent: u32,
     pub current_price: i128,
     pub launch_progress_percent: u32,
     pub is_launched: bool,
+    pub launch_ledger: u32,
+    pub transition_executed: bool,
+    pub launch_deadline: u32,
+    pub refunds_active: bool,
+    pub tokens_refunded: i128,
     pub creator: Address,
     pub creation_time: u64,
+    pub creation_ledger: u32,
+    pub curve_params: CurveParameters,
+    pub protection: LaunchProtection,
+    pub creator_allocation: CreatorAllocation,
+    pub creator_reserved: i128,
+    pub creator_vested: i128,
+    pub creator_claimed: i128,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct PurchaseResult {
     pub tokens_received: i128,
+    pub quote_spent: i128,
     pub new_price: i128,
     pub launch_triggered: bool,
 }
@@ -57,7 +279,7 @@ pub struct PurchaseResult {
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct SellResult {
-    pub xlm_received: i128,
+    pub quote_received: i128,
     pub new_price: i128,
 }
 
@@ -66,7 +288,57 @@ pub struct SellResult {
 pub struct LaunchEvent {
     pub token_address: Address,
     pub final_price: i128,
-    pub xlm_raised: i128,
+    pub quote_raised: i128,
     pub tokens_sold: i128,
     pub timestamp: u64,
-}
\ No newline at end of file
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Governance {
+    pub admins: Vec<Address>,                // Admin set
+    pub threshold: u32,                      // Approvals needed to execute an action
+    pub delay_ledgers: u32,                  // Timelock between proposal and execution
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum AdminAction {
+    SetFeeConfig(FeeConfig),
+    SetReferralShare(u32),
+    SetKeeperBounty(u32),
+    SetTradeLimits(TradeLimits),
+    SetTokenTradeLimits(String, TradeLimits),
+    ClearTokenTradeLimits(String),
+    ClearTokenHook(String),
+    SetCurvePreset(u32, CurvePreset),
+    RemoveCurvePreset(u32),
+    SetGovernance(Governance),
+    Unpause,
+    Shutdown,
+    Upgrade(BytesN<32>),
+    LockTokenCreation,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct QueuedAction {
+    pub action: AdminAction,
+    pub approvals: Vec<Address>,             // Admins who approved, starting with the proposer
+    pub eta_ledger: u32,                     // First ledger the action may execute
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Observation {
+    pub timestamp: u64,                      // Ledger timestamp of the trade
+    pub cumulative_price: i128,              // Sum of price * seconds up to `timestamp`
+    pub price: i128,                         // Spot price from `timestamp` onwards
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct OracleState {
+    pub observations: Vec<Observation>,      // Ring buffer of recent observations
+    pub newest: u32,                         // Index of the latest observation
+}
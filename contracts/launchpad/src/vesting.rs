@@ -0,0 +1,113 @@
+use crate::{
+    errors::ContractError,
+    types::CreatorAllocation,
+};
+
+const MAX_CREATOR_PERCENT: u32 = 10; // Creators may reserve at most 10% of supply
+
+pub struct Vesting;
+
+impl Vesting {
+    /// Validate a creator allocation and its vesting schedule
+    pub fn validate_allocation(allocation: &CreatorAllocation) -> Result<(), ContractError> {
+        if allocation.percent == 0 {
+            return Ok(());
+        }
+
+        if allocation.percent > MAX_CREATOR_PERCENT {
+            return Err(ContractError::InvalidVestingSchedule);
+        }
+
+        if allocation.vesting_ledgers == 0 || allocation.cliff_ledgers > allocation.vesting_ledgers {
+            return Err(ContractError::InvalidVestingSchedule);
+        }
+
+        Ok(())
+    }
+
+    /// Tokens reserved for the creator out of the total supply
+    pub fn reserved_amount(
+        allocation: &CreatorAllocation,
+        total_supply: i128,
+    ) -> Result<i128, ContractError> {
+        total_supply
+            .checked_mul(allocation.percent as i128)
+            .map(|scaled| scaled / 100)
+            .ok_or(ContractError::CalculationOverflow)
+    }
+
+    /// Tokens vested `elapsed_ledgers` after graduation
+    ///
+    /// Nothing vests before the cliff; from then on the reserve vests linearly
+    /// from graduation until `vesting_ledgers` have passed.
+    pub fn vested_amount(
+        allocation: &CreatorAllocation,
+        reserved: i128,
+        elapsed_ledgers: u32,
+    ) -> Result<i128, ContractError> {
+        if reserved == 0 || elapsed_ledgers < allocation.cliff_ledgers {
+            return Ok(0);
+        }
+
+        if elapsed_ledgers >= allocation.vesting_ledgers {
+            return Ok(reserved);
+        }
+
+        reserved
+            .checked_mul(elapsed_ledgers as i128)
+            .map(|scaled| scaled / allocation.vesting_ledgers as i128)
+            .ok_or(ContractError::CalculationOverflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_allocation() -> CreatorAllocation {
+        CreatorAllocation {
+            percent: 5,
+            cliff_ledgers: 100,
+            vesting_ledgers: 1000,
+        }
+    }
+
+    #[test]
+    fn test_reserved_amount() {
+        assert_eq!(Vesting::reserved_amount(&create_allocation(), 1_000_000).unwrap(), 50_000);
+        assert_eq!(Vesting::reserved_amount(&CreatorAllocation::none(), 1_000_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_cliff_and_linear_vesting() {
+        let allocation = create_allocation();
+
+        assert_eq!(Vesting::vested_amount(&allocation, 50_000, 0).unwrap(), 0);
+        assert_eq!(Vesting::vested_amount(&allocation, 50_000, 99).unwrap(), 0);
+
+        // The cliff releases everything vested so far at once
+        assert_eq!(Vesting::vested_amount(&allocation, 50_000, 100).unwrap(), 5_000);
+        assert_eq!(Vesting::vested_amount(&allocation, 50_000, 500).unwrap(), 25_000);
+        assert_eq!(Vesting::vested_amount(&allocation, 50_000, 1000).unwrap(), 50_000);
+        assert_eq!(Vesting::vested_amount(&allocation, 50_000, 5000).unwrap(), 50_000);
+    }
+
+    #[test]
+    fn test_validate_allocation() {
+        assert!(Vesting::validate_allocation(&create_allocation()).is_ok());
+        assert!(Vesting::validate_allocation(&CreatorAllocation::none()).is_ok());
+
+        let mut allocation = create_allocation();
+        allocation.percent = 11;
+        assert_eq!(Vesting::validate_allocation(&allocation), Err(ContractError::InvalidVestingSchedule));
+
+        let mut allocation = create_allocation();
+        allocation.cliff_ledgers = 1001;
+        assert_eq!(Vesting::validate_allocation(&allocation), Err(ContractError::InvalidVestingSchedule));
+
+        let mut allocation = create_allocation();
+        allocation.vesting_ledgers = 0;
+        allocation.cliff_ledgers = 0;
+        assert_eq!(Vesting::validate_allocation(&allocation), Err(ContractError::InvalidVestingSchedule));
+    }
+}
@@ -0,0 +1,5 @@
+-- On-chain token metadata set by the creator before graduation
+ALTER TABLE tokens
+    ADD COLUMN website TEXT,
+    ADD COLUMN socials TEXT[],
+    ADD COLUMN metadata_hash VARCHAR(64);
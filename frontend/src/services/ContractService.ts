@@ -164,7 +164,7 @@ export class ContractService {
           symbol: tokenData.symbol,
           totalSupply: tokenData.total_supply,
           tokensSold: tokenData.tokens_sold,
-          xlmRaised: tokenData.xlm_raised / 10000000, // Convert from stroops
+          xlmRaised: tokenData.quote_raised / 10000000, // Convert from stroops
           currentPrice: tokenData.current_price / 10000000,
           launchProgressPercent: tokenData.launch_progress_percent,
           isLaunched: tokenData.is_launched,